@@ -0,0 +1,27 @@
+//! A rate limiting proxy that processes of the same bot can share, refer to
+//! `daybreak::http::proxy::Proxy`
+//!
+//! Set `DAYBREAK_PROXY_ADDR` to change the address it listens on, which is
+//! `127.0.0.1:8080` by default, and `DAYBREAK_PROXY_UPSTREAM` to change the
+//! URL it forwards requests to, which is `https://discord.com` by default
+
+use std::{env, net::SocketAddr};
+
+use anyhow::Error;
+use daybreak::{http::proxy::Proxy, ContextConfig};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let addr: SocketAddr = env::var("DAYBREAK_PROXY_ADDR")
+        .as_deref()
+        .unwrap_or("127.0.0.1:8080")
+        .parse()?;
+    let upstream = env::var("DAYBREAK_PROXY_UPSTREAM");
+
+    Proxy::new(
+        upstream.as_deref().unwrap_or("https://discord.com"),
+        &ContextConfig::default(),
+    )
+    .serve(addr, |err| eprintln!("forwarding a request failed: {err:?}"))
+    .await
+}
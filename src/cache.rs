@@ -0,0 +1,235 @@
+use std::{
+    collections::HashMap,
+    sync::{MutexGuard, PoisonError},
+};
+
+use enumflags2::BitFlags;
+
+use super::Context;
+use crate::{
+    http::Request,
+    model::{
+        channel::{Channel, ChannelType, PermissionOverwrite},
+        guild::Guild,
+        member::Member,
+        permission::{self, Permissions},
+        Id,
+    },
+    UserError,
+};
+
+/// The bot's permissions that are cached to check requests before they're
+/// sent, shared by every clone of a context
+#[derive(Debug, Default)]
+pub(crate) struct PermissionCache {
+    /// The bot's permissions and roles in each guild
+    guilds: HashMap<Id, CachedGuild>,
+    /// The overwrites of each channel in the cached guilds
+    channels: HashMap<Id, CachedChannel>,
+}
+
+/// The bot's permissions and member in a guild
+#[derive(Debug)]
+struct CachedGuild {
+    /// The bot's permissions in the guild, not counting channel overwrites
+    /// or restricting them if the bot is timed out
+    permissions: BitFlags<Permissions>,
+    /// The bot's member in the guild, to apply the overwrites for its roles
+    /// and restrict the permissions while it's timed out
+    member: Member,
+}
+
+/// The overwrites of a channel, or the parent of a thread
+#[derive(Debug)]
+struct CachedChannel {
+    /// The ID of the guild the channel is in
+    guild_id: Id,
+    /// The permission overwrites of the channel, empty for threads
+    overwrites: Vec<PermissionOverwrite>,
+    /// The ID of the parent channel if it's a thread, whose overwrites it
+    /// uses
+    parent_id: Option<Id>,
+}
+
+impl PermissionCache {
+    /// The bot's permissions in the guild, if it's cached
+    fn guild_permissions(&self, guild_id: Id) -> Option<BitFlags<Permissions>> {
+        let guild = self.guilds.get(&guild_id)?;
+
+        Some(permission::restrict(guild.permissions, &guild.member))
+    }
+
+    /// The bot's permissions in the channel, if the channel, its guild and
+    /// its parent if it's a thread are cached
+    fn channel_permissions(&self, channel_id: Id) -> Option<BitFlags<Permissions>> {
+        let mut channel = self.channels.get(&channel_id)?;
+        if let Some(parent_id) = channel.parent_id {
+            channel = self.channels.get(&parent_id)?;
+        }
+        let guild = self.guilds.get(&channel.guild_id)?;
+
+        Some(permission::restrict(
+            permission::apply_overwrites(
+                guild.permissions,
+                &channel.overwrites,
+                channel.guild_id,
+                &guild.member,
+            ),
+            &guild.member,
+        ))
+    }
+
+    /// Cache the overwrites of the channel in the guild, or its parent if
+    /// it's a thread
+    fn insert_channel(&mut self, channel: &Channel, guild_id: Id) {
+        let cached_channel = if is_thread(channel.channel_type) {
+            let Some(parent_id) = channel.parent_id else {
+                return;
+            };
+            CachedChannel {
+                guild_id,
+                overwrites: vec![],
+                parent_id: Some(parent_id),
+            }
+        } else {
+            CachedChannel {
+                guild_id,
+                overwrites: channel.permission_overwrites.clone().unwrap_or_default(),
+                parent_id: None,
+            }
+        };
+
+        self.channels.extend([(channel.id, cached_channel)]);
+    }
+}
+
+impl Context {
+    /// Compute the bot's permissions in the guild and its channels and cache
+    /// them, so that the permissions requests to them require are checked
+    /// before they're sent
+    ///
+    /// `member` is the bot's member in the guild, with its `user` set, the
+    /// channels and threads are cached from `guild.channels` and
+    /// `guild.threads`, which are only set in the `GUILD_CREATE` event
+    ///
+    /// The permissions aren't updated automatically, so call this again when
+    /// the guild, its roles or the bot's member change, such as in the
+    /// `GUILD_UPDATE`, `GUILD_ROLE_*` and `GUILD_MEMBER_UPDATE` events, and
+    /// use [`Self::cache_channel`] when a channel changes, whether the bot is
+    /// timed out is checked on every request, so the end of a timeout doesn't
+    /// need to be cached
+    ///
+    /// Requests to guilds and channels that aren't cached aren't checked
+    pub fn cache_permissions(&self, guild: &Guild, member: &Member) {
+        let mut cache = self.permission_cache();
+        cache.guilds.extend([(
+            guild.id,
+            CachedGuild {
+                permissions: guild.base_permissions(member),
+                member: member.clone(),
+            },
+        )]);
+
+        for channel in guild.channels.iter().chain(&guild.threads).flatten() {
+            cache.insert_channel(channel, guild.id);
+        }
+    }
+
+    /// Cache the overwrites of the channel, such as in the `CHANNEL_CREATE`,
+    /// `CHANNEL_UPDATE` and `THREAD_CREATE` events
+    ///
+    /// Threads use the overwrites of their parent channel, so their
+    /// permissions are only known while their parent is cached, and updating
+    /// the parent updates them too, channels outside guilds aren't cached
+    pub fn cache_channel(&self, channel: &Channel) {
+        if let Some(guild_id) = channel.guild_id {
+            self.permission_cache().insert_channel(channel, guild_id);
+        }
+    }
+
+    /// Remove the bot's cached permissions in the guild and its channels,
+    /// such as when it leaves the guild
+    pub fn uncache_permissions(&self, guild_id: Id) {
+        let mut cache = self.permission_cache();
+        cache.guilds.retain(|id, _| *id != guild_id);
+        cache
+            .channels
+            .retain(|_, channel| channel.guild_id != guild_id);
+    }
+
+    /// Remove the cached overwrites of the channel, such as when it's
+    /// deleted
+    pub fn uncache_channel(&self, channel_id: Id) {
+        self.permission_cache()
+            .channels
+            .retain(|id, _| *id != channel_id);
+    }
+
+    /// The bot's cached permissions in the guild, not counting channel
+    /// overwrites, or `None` if they aren't cached
+    #[must_use]
+    pub fn permissions(&self, guild_id: Id) -> Option<BitFlags<Permissions>> {
+        self.permission_cache().guild_permissions(guild_id)
+    }
+
+    /// The bot's cached permissions in the channel, with its overwrites
+    /// applied, or `None` if the channel, its guild or the parent of a thread
+    /// isn't cached
+    #[must_use]
+    pub fn channel_permissions(&self, channel_id: Id) -> Option<BitFlags<Permissions>> {
+        self.permission_cache().channel_permissions(channel_id)
+    }
+
+    /// The bot's cached permissions in the request's channel, or in its guild
+    /// if it isn't made to a channel
+    ///
+    /// This is `None` if the channel or the guild isn't cached, the guild's
+    /// permissions aren't used for channels that aren't cached since their
+    /// overwrites might allow the request
+    pub(crate) fn request_permissions(&self, request: &Request) -> Option<BitFlags<Permissions>> {
+        let cache = self.permission_cache();
+        match request.channel_id {
+            Some(channel_id) => cache.channel_permissions(channel_id),
+            None => request
+                .guild_id
+                .and_then(|guild_id| cache.guild_permissions(guild_id)),
+        }
+    }
+
+    /// Check that the bot has the permissions the request requires in its
+    /// channel, or in its guild if it isn't made to a channel, requests to
+    /// channels or guilds that aren't cached aren't checked
+    pub(crate) fn check_permissions(&self, request: &Request) -> Result<(), UserError> {
+        let permissions = self.request_permissions(request);
+
+        let missing_permissions = permissions.map_or_else(BitFlags::empty, |permissions| {
+            request.required_permissions & !permissions
+        });
+        if missing_permissions.is_empty() {
+            Ok(())
+        } else {
+            Err(UserError::missing_permissions(
+                missing_permissions,
+                request.channel_id,
+            ))
+        }
+    }
+
+    /// Lock the permission cache
+    fn permission_cache(&self) -> MutexGuard<'_, PermissionCache> {
+        self.permissions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Whether the channel type is a thread, which uses the overwrites of its
+/// parent channel
+const fn is_thread(channel_type: ChannelType) -> bool {
+    matches!(
+        channel_type,
+        ChannelType::GuildNewsThread
+            | ChannelType::GuildPublicThread
+            | ChannelType::GuildPrivateThread
+    )
+}
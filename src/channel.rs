@@ -0,0 +1,80 @@
+use anyhow::Error;
+use enumflags2::BitFlag;
+use futures_util::stream::BoxStream;
+use hyper::Method;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+
+use super::Context;
+use crate::{
+    http::{pagination::Direction, Request},
+    model::{message::Message, permission::Permissions, user::User, Id},
+};
+
+impl Context {
+    /// Walk the messages of the channel, from the newest to the oldest
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use anyhow::Error;
+    /// use daybreak::{model::Id, Context};
+    /// use futures_util::StreamExt;
+    ///
+    /// async fn count_messages(ctx: &Context, channel_id: Id) -> Result<usize, Error> {
+    ///     let mut count = 0;
+    ///     let mut messages = ctx.messages(channel_id);
+    ///     while let Some(message) = messages.next().await {
+    ///         message?;
+    ///         count += 1;
+    ///     }
+    ///     Ok(count)
+    /// }
+    /// ```
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/resources/channel#get-channel-messages"
+    )]
+    #[doc = http_errors_doc!()]
+    #[must_use]
+    pub fn messages(&self, channel_id: Id) -> BoxStream<'_, Result<Message, Error>> {
+        self.paginate(
+            Request::new(
+                Permissions::ViewChannel | Permissions::ReadMessageHistory,
+                Method::GET,
+                format!("/channels/{channel_id}/messages"),
+            )
+            .with_channel(channel_id),
+            Direction::Before,
+            100,
+            |message: &Message| Some(message.id),
+        )
+    }
+
+    /// Walk the users that reacted to the message with the given emoji
+    ///
+    /// The emoji is either a unicode emoji or a custom emoji in the
+    /// `name:id` format
+    #[doc = discord_url!("https://discord.com/developers/docs/resources/channel#get-reactions")]
+    #[doc = http_errors_doc!()]
+    #[must_use]
+    pub fn reaction_users(
+        &self,
+        channel_id: Id,
+        message_id: Id,
+        emoji: &str,
+    ) -> BoxStream<'_, Result<User, Error>> {
+        self.paginate(
+            Request::new(
+                Permissions::empty(),
+                Method::GET,
+                format!(
+                    "/channels/{channel_id}/messages/{message_id}/reactions/{}",
+                    utf8_percent_encode(emoji, NON_ALPHANUMERIC)
+                ),
+            )
+            .with_channel(channel_id),
+            Direction::After,
+            100,
+            |user: &User| Some(user.id),
+        )
+    }
+}
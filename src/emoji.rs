@@ -16,34 +16,47 @@ impl Context {
     #[doc = discord_url!("https://discord.com/developers/docs/resources/emoji#list-guild-emojis")]
     #[doc = http_errors_doc!()]
     pub async fn emojis(&self, guild_id: Id) -> Result<Vec<Emoji>, Error> {
-        self.empty_request(Request::new(
-            Permissions::empty(),
-            Method::GET,
-            format!("/guilds/{guild_id}/emojis"),
-        ))
+        self.empty_request(
+            Request::new(
+                Permissions::empty(),
+                Method::GET,
+                format!("/guilds/{guild_id}/emojis"),
+            )
+            .with_guild(guild_id),
+        )
         .await
     }
 
     #[doc = discord_url!("https://discord.com/developers/docs/resources/emoji#get-guild-emoji")]
     #[doc = http_errors_doc!()]
     pub async fn emoji(&self, guild_id: Id, emoji_id: Id) -> Result<Emoji, Error> {
-        self.empty_request(Request::new(
-            Permissions::empty(),
-            Method::GET,
-            format!("/guilds/{guild_id}/emojis/{emoji_id}"),
-        ))
+        self.empty_request(
+            Request::new(
+                Permissions::empty(),
+                Method::GET,
+                format!("/guilds/{guild_id}/emojis/{emoji_id}"),
+            )
+            .with_guild(guild_id),
+        )
         .await
     }
 
     #[doc = discord_url!("https://discord.com/developers/docs/resources/emoji#create-guild-emoji")]
     #[doc = http_errors_doc!()]
-    pub async fn create(&self, guild_id: Id, emoji: CreateEmoji) -> Result<Emoji, Error> {
+    pub async fn create(
+        &self,
+        guild_id: Id,
+        emoji: CreateEmoji,
+        reason: Option<&str>,
+    ) -> Result<Emoji, Error> {
         self.request_with_params(
             Request::new(
                 Permissions::ManageEmojisAndStickers.into(),
                 Method::POST,
                 format!("/guilds/{guild_id}/emojis"),
-            ),
+            )
+            .with_guild(guild_id)
+            .with_reason(reason)?,
             emoji,
         )
         .await
@@ -56,13 +69,16 @@ impl Context {
         guild_id: Id,
         emoji_id: Id,
         emoji: EditEmoji,
+        reason: Option<&str>,
     ) -> Result<Emoji, Error> {
         self.request_with_params(
             Request::new(
                 Permissions::ManageEmojisAndStickers.into(),
                 Method::PATCH,
                 format!("/guilds/{guild_id}/emojis/{emoji_id}"),
-            ),
+            )
+            .with_guild(guild_id)
+            .with_reason(reason)?,
             emoji,
         )
         .await
@@ -70,12 +86,21 @@ impl Context {
 
     #[doc = discord_url!("https://discord.com/developers/docs/resources/emoji#delete-guild-emoji")]
     #[doc = http_errors_doc!()]
-    pub async fn delete(&self, guild_id: Id, emoji_id: Id) -> Result<u16, Error> {
-        self.empty_request(Request::new(
-            Permissions::ManageEmojisAndStickers.into(),
-            Method::DELETE,
-            format!("/guilds/{guild_id}/emojis/{emoji_id}"),
-        ))
+    pub async fn delete(
+        &self,
+        guild_id: Id,
+        emoji_id: Id,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        self.empty_request(
+            Request::new(
+                Permissions::ManageEmojisAndStickers.into(),
+                Method::DELETE,
+                format!("/guilds/{guild_id}/emojis/{emoji_id}"),
+            )
+            .with_guild(guild_id)
+            .with_reason(reason)?,
+        )
         .await
     }
 }
@@ -0,0 +1,48 @@
+use anyhow::Error;
+use enumflags2::BitFlag;
+use futures_util::stream::BoxStream;
+use hyper::Method;
+
+use super::Context;
+use crate::{
+    http::{pagination::Direction, Request},
+    model::{guild::Ban, member::Member, permission::Permissions, Id},
+};
+
+impl Context {
+    /// Walk the members of the guild, this requires the `GUILD_MEMBERS` intent
+    #[doc = discord_url!("https://discord.com/developers/docs/resources/guild#list-guild-members")]
+    #[doc = http_errors_doc!()]
+    #[must_use]
+    pub fn members(&self, guild_id: Id) -> BoxStream<'_, Result<Member, Error>> {
+        self.paginate(
+            Request::new(
+                Permissions::empty(),
+                Method::GET,
+                format!("/guilds/{guild_id}/members"),
+            )
+            .with_guild(guild_id),
+            Direction::After,
+            1000,
+            |member: &Member| member.user.as_ref().map(|user| user.id),
+        )
+    }
+
+    /// Walk the bans of the guild
+    #[doc = discord_url!("https://discord.com/developers/docs/resources/guild#get-guild-bans")]
+    #[doc = http_errors_doc!()]
+    #[must_use]
+    pub fn bans(&self, guild_id: Id) -> BoxStream<'_, Result<Ban, Error>> {
+        self.paginate(
+            Request::new(
+                Permissions::BanMembers.into(),
+                Method::GET,
+                format!("/guilds/{guild_id}/bans"),
+            )
+            .with_guild(guild_id),
+            Direction::After,
+            1000,
+            |ban: &Ban| Some(ban.user.id),
+        )
+    }
+}
@@ -1,33 +1,188 @@
-use anyhow::Error;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context as _, Error};
 use enumflags2::BitFlags;
-use hyper::{body::to_bytes, client::HttpConnector, Body, Client, Method, Request as HyperRequest};
+use hyper::{
+    body::{to_bytes, Bytes, HttpBody},
+    client::HttpConnector,
+    Body, Client, Method, Request as HyperRequest, Response, StatusCode,
+};
 use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tokio::time::{sleep, timeout};
+
+use crate::{
+    http::{
+        error::{
+            DiscordApiError, InvalidRequestLimitError, RateLimitError, RateLimitResponse,
+            RateLimitScope, ResponseTooLargeError, TimeoutError,
+        },
+        invalid_request::InvalidRequestAction,
+        middleware::ResponseInfo,
+        rate_limit::Route,
+    },
+    model::{permission::Permissions, Id},
+    Context, UserError,
+};
 
-use crate::{model::permission::Permissions, Context, UserError};
+/// Recording requests to a file and replaying them, for offline tests
+pub mod cassette;
+/// Errors returned when making requests
+pub mod error;
+/// Guarding against the ban on IPs that make too many invalid requests
+pub mod invalid_request;
+/// Hooks called around every request, such as for tracing or metrics
+pub mod middleware;
+/// Encoding of `multipart/form-data` bodies for requests with files
+pub(crate) mod multipart;
+/// Walking paginated resources as streams
+pub(crate) mod pagination;
+/// A rate limiting proxy that processes of the same bot can share
+#[cfg(feature = "proxy")]
+pub mod proxy;
+/// Rate limit handling, shared by every clone of a context
+pub mod rate_limit;
+/// Retrying requests that failed because of transient errors
+pub mod retry;
+/// Sending HTTP requests, which can be replaced for tests or custom connectors
+pub mod transport;
 
-/// The client type used in this crate
+/// Whether the error is a hyper error from failing to connect, in which case
+/// the request never reached Discord
+fn is_connect_error(err: &Error) -> bool {
+    err.downcast_ref::<hyper::Error>()
+        .map_or(false, hyper::Error::is_connect)
+}
+
+/// A random number, this isn't cryptographically secure
+pub(crate) fn random() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// The maximum number of characters in an audit log reason
+const MAX_REASON_LENGTH: usize = 512;
+
+/// The maximum number of bytes of a response body to show in errors
+const MAX_BODY_SNIPPET_LENGTH: usize = 256;
+
+/// The default client type used in this crate
 pub(crate) type Http = Client<HttpsConnector<HttpConnector>>;
 
-/// Creates the HTTP client
-pub(crate) fn create() -> Http {
-    let connector = HttpsConnectorBuilder::new()
-        .with_webpki_roots()
-        .https_only()
-        .enable_http2()
-        .build();
+/// Creates the default HTTP client, which allows plain HTTP only if
+/// `allow_http` is true
+pub(crate) fn create(allow_http: bool) -> Http {
+    let builder = HttpsConnectorBuilder::new().with_webpki_roots();
+    let connector = if allow_http {
+        builder.https_or_http()
+    } else {
+        builder.https_only()
+    }
+    .enable_http2()
+    .build();
     Client::builder().build(connector)
 }
 
+/// Creates the HTTP client used to send requests to a proxy, which uses
+/// HTTP/2 over plain HTTP
+pub(crate) fn create_proxied() -> Client<HttpConnector> {
+    Client::builder().http2_only(true).build_http()
+}
+
+/// Deserialize the body of a successful response
+///
+/// Responses with no content, such as `204 No Content`, are deserialized
+/// from `null`, so they can be deserialized into `()` or an `Option`
+pub(crate) fn deserialize_body<T: DeserializeOwned>(
+    status: StatusCode,
+    bytes: &[u8],
+) -> Result<T, Error> {
+    if status == StatusCode::NO_CONTENT || bytes.is_empty() {
+        return T::deserialize(Value::Null)
+            .with_context(|| format!("the response has no content, status: {status}"));
+    }
+
+    serde_json::from_slice(bytes).with_context(|| {
+        format!(
+            "the response can't be deserialized, status: {status}, body: {}",
+            body_snippet(bytes)
+        )
+    })
+}
+
+/// The start of the body as text, to show in errors
+pub(crate) fn body_snippet(bytes: &[u8]) -> String {
+    match bytes.get(..MAX_BODY_SNIPPET_LENGTH) {
+        Some(start) if bytes.len() > MAX_BODY_SNIPPET_LENGTH => format!(
+            "{}... ({} bytes)",
+            String::from_utf8_lossy(start),
+            bytes.len()
+        ),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Read the body of the response, returning a [`ResponseTooLargeError`] if
+/// it's bigger than `max_size` bytes
+pub(crate) async fn read_body(
+    response: Response<Body>,
+    max_size: Option<usize>,
+) -> Result<Bytes, Error> {
+    let max_size = match max_size {
+        Some(max_size) => max_size,
+        None => return Ok(to_bytes(response.into_body()).await?),
+    };
+
+    let content_length = rate_limit::header::<usize>(response.headers(), "content-length");
+    if content_length.map_or(false, |length| length > max_size) {
+        return Err(ResponseTooLargeError { max_size }.into());
+    }
+
+    let mut body = response.into_body();
+    let mut bytes = Vec::with_capacity(content_length.unwrap_or_default());
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if bytes.len().saturating_add(chunk.len()) > max_size {
+            return Err(ResponseTooLargeError { max_size }.into());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes.into())
+}
+
 /// An HTTP request to be made to the Discord HTTP API
 #[derive(Clone, Debug)]
 pub struct Request {
     /// The required permissions to make the request
-    required_permissions: BitFlags<Permissions>,
+    pub(crate) required_permissions: BitFlags<Permissions>,
+    /// The guild the request is made in, to check the required permissions
+    /// against the bot's permissions in it
+    pub(crate) guild_id: Option<Id>,
+    /// The channel the request is made in, to check the required permissions
+    /// against the bot's permissions in it
+    pub(crate) channel_id: Option<Id>,
     /// The method of the request
-    method: Method,
+    pub(crate) method: Method,
     /// The endpoint URL of the request
-    endpoint: String,
+    pub(crate) endpoint: String,
+    /// The files to upload with the request
+    pub(crate) files: Vec<File>,
+    /// The URL-encoded query string of the request
+    pub(crate) query: Option<String>,
+    /// The percent-encoded reason to show in the audit log
+    pub(crate) reason: Option<String>,
+    /// How long to wait for the request to complete, overriding
+    /// `ContextConfig.timeout`
+    pub(crate) timeout: Option<Duration>,
+    /// Whether to send the params as `application/x-www-form-urlencoded`
+    /// instead of JSON
+    pub(crate) form: bool,
 }
 
 impl Request {
@@ -40,9 +195,217 @@ impl Request {
     ) -> Self {
         Self {
             required_permissions,
+            guild_id: None,
+            channel_id: None,
             method,
             endpoint,
+            files: Vec::new(),
+            query: None,
+            reason: None,
+            timeout: None,
+            form: false,
+        }
+    }
+
+    /// Set the query string parameters of the request
+    ///
+    /// The query is URL-encoded, fields that are `None` are skipped, it's
+    /// appended to the query set before, if any
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use daybreak::{http::Request, model::Id};
+    /// use enumflags2::BitFlag;
+    /// use hyper::Method;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct GetMessages {
+    ///     before: Option<Id>,
+    ///     limit: Option<u8>,
+    /// }
+    ///
+    /// let request = Request::new(
+    ///     BitFlag::empty(),
+    ///     Method::GET,
+    ///     "/channels/1234/messages".to_owned(),
+    /// )
+    /// .with_query(&GetMessages {
+    ///     before: Some(Id(5678)),
+    ///     limit: None,
+    /// })?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `anyhow::Error` if the query can't be URL-encoded, such as
+    /// when it isn't a struct or a map
+    pub fn with_query(mut self, query: &impl Serialize) -> Result<Self, Error> {
+        let encoded = serde_urlencoded::to_string(query)?;
+        self.query = Some(match self.query {
+            Some(existing) if !existing.is_empty() => format!("{existing}&{encoded}"),
+            _ => encoded,
+        });
+        Ok(self)
+    }
+
+    /// The method of the request
+    #[must_use]
+    pub const fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// The endpoint of the request, without the query string
+    #[must_use]
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// The rate limit route of the request, which is the method and the
+    /// endpoint with the parameters other than the major parameter replaced
+    ///
+    /// This is useful to group requests, such as for metrics
+    #[must_use]
+    pub fn route(&self) -> String {
+        Route::new(&self.method, &self.endpoint).to_string()
+    }
+
+    /// Set the reason to show in the guild's audit log for the request, if
+    /// it's given
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/resources/audit-log#audit-log-entry-object"
+    )]
+    /// # Errors
+    ///
+    /// Returns [`UserError::AuditLogReasonTooLong`] if the reason is longer
+    /// than 512 characters
+    pub fn with_reason(mut self, reason: Option<&str>) -> Result<Self, Error> {
+        if let Some(r) = reason {
+            if r.chars().count() > MAX_REASON_LENGTH {
+                return Err(UserError::AuditLogReasonTooLong.into());
+            }
+            self.reason = Some(utf8_percent_encode(r, NON_ALPHANUMERIC).to_string());
+        }
+        Ok(self)
+    }
+
+    /// Upload the given files with the request
+    ///
+    /// The request is then sent as `multipart/form-data`, with the params in
+    /// the `payload_json` part, so the params must be a JSON object if given
+    ///
+    /// The files are added to the `attachments` param, referencing them by
+    /// their index, they're usually returned as
+    /// [`Attachment`](crate::model::message::Attachment)s in the response
+    #[doc = discord_url!("https://discord.com/developers/docs/reference#uploading-files")]
+    #[must_use]
+    pub fn with_files(mut self, files: Vec<File>) -> Self {
+        self.files = files;
+        self
+    }
+
+    /// Send the params of the request as `application/x-www-form-urlencoded`
+    /// instead of JSON, such as for OAuth2 endpoints
+    ///
+    /// This is ignored if the request has files
+    #[must_use]
+    pub const fn with_form(mut self) -> Self {
+        self.form = true;
+        self
+    }
+
+    /// Set the guild the request is made in
+    ///
+    /// If the bot's permissions in the guild are cached with
+    /// [`Context::cache_permissions`], the required permissions are checked
+    /// before sending the request, returning
+    /// [`UserError::MissingPermissions`] if the bot doesn't have them
+    #[must_use]
+    pub const fn with_guild(mut self, guild_id: Id) -> Self {
+        self.guild_id = Some(guild_id);
+        self
+    }
+
+    /// Set the channel the request is made in
+    ///
+    /// If the bot's permissions in the channel are cached with
+    /// [`Context::cache_permissions`] or [`Context::cache_channel`], the
+    /// required permissions are checked against them, with the channel's
+    /// overwrites applied, before sending the request, returning
+    /// [`UserError::MissingChannelPermissions`] if the bot doesn't have them,
+    /// requests to channels that aren't cached aren't checked, even if their
+    /// guild is
+    #[must_use]
+    pub const fn with_channel(mut self, channel_id: Id) -> Self {
+        self.channel_id = Some(channel_id);
+        self
+    }
+
+    /// Set how long to wait for the request to complete, overriding
+    /// `ContextConfig.timeout`
+    ///
+    /// This includes the time spent waiting for the rate limiter and
+    /// retrying the request, a [`TimeoutError`] is returned if it's exceeded
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+/// A file to upload with a request
+#[derive(Clone, Debug)]
+pub struct File {
+    /// The name of the file, including its extension
+    pub filename: String,
+    /// The MIME type of the file, such as `image/png`
+    pub content_type: String,
+    /// The contents of the file
+    pub bytes: Vec<u8>,
+    /// The description of the file, this is the alt text for images
+    pub description: Option<String>,
+}
+
+/// The encoded body of a request
+#[derive(Clone, Debug)]
+struct RequestBody {
+    /// The value of the `Content-Type` header
+    content_type: String,
+    /// The encoded body
+    bytes: Bytes,
+}
+
+impl RequestBody {
+    /// Encode the body of the given request with the given params
+    ///
+    /// The body is JSON, `multipart/form-data` if the request has files, or
+    /// `application/x-www-form-urlencoded` if it's set to be a form
+    fn new(request: &Request, params: Option<impl Serialize>) -> Result<Option<Self>, Error> {
+        if !request.files.is_empty() {
+            let boundary = multipart::boundary();
+            return Ok(Some(Self {
+                bytes: multipart::encode(params, &request.files, &boundary)?.into(),
+                content_type: format!("multipart/form-data; boundary={boundary}"),
+            }));
         }
+
+        params
+            .map(|p| {
+                Ok(if request.form {
+                    Self {
+                        content_type: "application/x-www-form-urlencoded".to_owned(),
+                        bytes: serde_urlencoded::to_string(&p)?.into(),
+                    }
+                } else {
+                    Self {
+                        content_type: "application/json".to_owned(),
+                        bytes: serde_json::to_vec(&p)?.into(),
+                    }
+                })
+            })
+            .transpose()
     }
 }
 
@@ -61,7 +424,7 @@ impl Context {
     /// https://discord.com/developers/docs/resources/auto-moderation#delete-auto-moderation-rule
     ///
     /// ```rust
-    /// use anyhow::Error;
+    /// use anyhow::{Context as _, Error};
     /// use daybreak::{
     ///     http::Request,
     ///     model::{
@@ -81,6 +444,7 @@ impl Context {
     /// static CTX: Lazy<Context> = Lazy::new(|| {
     ///     Context::new(&ContextConfig {
     ///         token: env!("DAYBREAK_BOT_TOKEN"),
+    ///         ..ContextConfig::default()
     ///     })
     /// });
     ///
@@ -88,11 +452,14 @@ impl Context {
     ///     guild_id: Id,
     ///     auto_moderation_rule_id: Id,
     /// ) -> Result<(), Error> {
-    ///     CTX.empty_request(Request::new(
-    ///         Permissions::ManageGuild.into(),
-    ///         Method::DELETE,
-    ///         format!("/guilds/{guild_id}/auto-moderation/rules/{auto_moderation_rule_id}"),
-    ///     ))
+    ///     CTX.empty_request(
+    ///         Request::new(
+    ///             Permissions::ManageGuild.into(),
+    ///             Method::DELETE,
+    ///             format!("/guilds/{guild_id}/auto-moderation/rules/{auto_moderation_rule_id}"),
+    ///         )
+    ///         .with_guild(guild_id),
+    ///     )
     ///     .await
     /// }
     /// ```
@@ -118,7 +485,7 @@ impl Context {
     /// https://discord.com/developers/docs/resources/auto-moderation#modify-auto-moderation-rule
     ///
     /// ```rust
-    /// use anyhow::Error;
+    /// use anyhow::{Context as _, Error};
     /// use daybreak::{
     ///     http::Request,
     ///     model::{
@@ -138,6 +505,7 @@ impl Context {
     /// static CTX: Lazy<Context> = Lazy::new(|| {
     ///     Context::new(&ContextConfig {
     ///         token: env!("DAYBREAK_BOT_TOKEN"),
+    ///         ..ContextConfig::default()
     ///     })
     /// });
     ///
@@ -162,7 +530,8 @@ impl Context {
     ///             Permissions::ManageGuild.into(),
     ///             Method::PATCH,
     ///             format!("/guilds/{guild_id}/auto-moderation/rules/{auto_moderation_rule_id}"),
-    ///         );
+    ///         )
+    ///         .with_guild(guild_id);
     ///         CTX.request_with_params(request, self).await
     ///     }
     /// }
@@ -176,39 +545,237 @@ impl Context {
         self.request(request, Some(params)).await
     }
 
-    /// Send the given request to Discord, returning the expected type
+    /// Send the given request to Discord, returning the expected type, or a
+    /// [`TimeoutError`] if it doesn't complete within its timeout
     #[doc = http_errors_doc!()]
     async fn request<T: DeserializeOwned + Send>(
         &self,
         request: Request,
         params: Option<impl Serialize + Send>,
     ) -> Result<T, Error> {
-        if !self.permissions.contains(request.required_permissions) {
-            return Err(UserError::MissingPermissions(
-                !(self.permissions & request.required_permissions),
-            )
-            .into());
+        let duration = match request.timeout.or(self.timeout) {
+            Some(duration) => duration,
+            None => return self.request_without_timeout(request, params).await,
+        };
+
+        let route = request.route();
+        timeout(duration, self.request_without_timeout(request, params))
+            .await
+            .map_err(|_| TimeoutError {
+                route,
+                timeout: duration,
+            })?
+    }
+
+    /// Send the given request to Discord, returning the expected type
+    #[doc = http_errors_doc!()]
+    async fn request_without_timeout<T: DeserializeOwned + Send>(
+        &self,
+        request: Request,
+        params: Option<impl Serialize + Send>,
+    ) -> Result<T, Error> {
+        self.check_permissions(&request)?;
+
+        let body = RequestBody::new(&request, params)?;
+        let response = self
+            .send(&request, || self.hyper_request(&request, body.clone()))
+            .await?;
+
+        let status = response.status();
+        let bytes = read_body(response, self.max_response_size).await?;
+
+        if !status.is_success() {
+            let api_error = DiscordApiError::new(status, &bytes);
+            return Err(
+                match api_error.user_error(&request, self.request_permissions(&request)) {
+                    Some(user_error) => Error::new(api_error).context(user_error),
+                    None => api_error.into(),
+                },
+            );
         }
 
-        let request_body = if let Some(p) = params {
-            serde_json::to_string(&p)?.into()
-        } else {
-            Body::empty()
-        };
+        deserialize_body(status, &bytes)
+    }
+
+    /// Send the request, waiting for the rate limiter and retrying it if it's
+    /// rate limited or fails because of a transient error
+    ///
+    /// `hyper_request` builds the hyper request to send on every attempt
+    async fn send(
+        &self,
+        request: &Request,
+        hyper_request: impl Fn() -> Result<HyperRequest<Body>, Error> + Send + Sync,
+    ) -> Result<Response<Body>, Error> {
+        let route = Route::new(&request.method, &request.endpoint);
+
+        let mut attempt = 1_u8;
+        let mut rate_limit_retries = 0_u8;
+        loop {
+            if let Some(wait) = self
+                .invalid_requests
+                .wait_time(self.invalid_request_policy.threshold)
+            {
+                match self.invalid_request_policy.action {
+                    InvalidRequestAction::Refuse => {
+                        return Err(InvalidRequestLimitError {
+                            count: self.invalid_requests.count(),
+                            retry_after: wait,
+                        }
+                        .into())
+                    }
+                    InvalidRequestAction::Delay => {
+                        sleep(wait).await;
+                        continue;
+                    }
+                }
+            }
+
+            let ticket = match &self.rate_limiter {
+                Some(rate_limiter) => Some(rate_limiter.acquire(route.clone()).await),
+                None => None,
+            };
+            let response = match self.send_attempt(request, hyper_request()?).await {
+                Ok(response) => response,
+                Err(err) => {
+                    drop(ticket);
+                    match self.retry_policy.retry_delay(
+                        attempt,
+                        &request.method,
+                        !is_connect_error(&err),
+                    ) {
+                        Some(delay) => {
+                            sleep(delay).await;
+                            attempt = attempt.saturating_add(1);
+                            continue;
+                        }
+                        None => return Err(err),
+                    }
+                }
+            };
+            if let Some(ticket) = &ticket {
+                ticket.update(response.headers());
+            }
+            let scope = rate_limit::header::<String>(response.headers(), "x-ratelimit-scope")
+                .as_deref()
+                .and_then(RateLimitScope::from_header);
+            self.invalid_requests.record(response.status(), scope);
+
+            if response.status().is_server_error() {
+                if let Some(delay) = self
+                    .retry_policy
+                    .retry_delay(attempt, &request.method, true)
+                {
+                    drop(ticket);
+                    sleep(delay).await;
+                    attempt = attempt.saturating_add(1);
+                    continue;
+                }
+            }
+
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+
+            let headers = response.headers().clone();
+            let rate_limit = RateLimitResponse::new(
+                &headers,
+                &read_body(response, self.max_response_size).await?,
+            )?;
+            let retry_after = Duration::try_from_secs_f64(rate_limit.retry_after)?;
+            let global = rate_limit.global || scope == Some(RateLimitScope::Global);
 
-        let hyper_request = HyperRequest::builder()
-            .method(request.method)
-            .uri(format!("https://discord.com/api/v10{}", request.endpoint))
+            match (&self.rate_limiter, &ticket) {
+                (Some(rate_limiter), _) if global => rate_limiter.pause_global(retry_after),
+                (_, Some(ticket)) => ticket.pause(retry_after),
+                _ => {}
+            }
+
+            if rate_limit_retries >= self.max_rate_limit_retries {
+                return Err(RateLimitError {
+                    route: route.to_string(),
+                    retry_after,
+                    global,
+                    scope,
+                    message: rate_limit.message,
+                }
+                .into());
+            }
+            rate_limit_retries = rate_limit_retries.saturating_add(1);
+
+            if self.rate_limiter.is_none() {
+                drop(ticket);
+                sleep(retry_after).await;
+            }
+        }
+    }
+
+    /// Send the hyper request once, calling the middleware around it
+    async fn send_attempt(
+        &self,
+        request: &Request,
+        mut hyper_request: HyperRequest<Body>,
+    ) -> Result<Response<Body>, Error> {
+        for middleware in &self.middleware {
+            middleware.on_request(request, &mut hyper_request);
+        }
+
+        let sent_at = Instant::now();
+        let result = self.http.send(hyper_request).await;
+        let latency = sent_at.elapsed();
+
+        match &result {
+            Ok(response) => {
+                let info = ResponseInfo {
+                    status: response.status(),
+                    headers: response.headers(),
+                    latency,
+                };
+                for middleware in &self.middleware {
+                    middleware.on_response(request, &info);
+                }
+            }
+            Err(err) => {
+                for middleware in &self.middleware {
+                    middleware.on_error(request, err, latency);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Build the hyper request to send to Discord from the given request and
+    /// body
+    fn hyper_request(
+        &self,
+        request: &Request,
+        body: Option<RequestBody>,
+    ) -> Result<HyperRequest<Body>, Error> {
+        let mut builder = HyperRequest::builder()
+            .method(request.method.clone())
+            .uri(match &request.query {
+                Some(query) if !query.is_empty() => {
+                    format!("{}{}?{query}", self.api_url, request.endpoint)
+                }
+                _ => format!("{}{}", self.api_url, request.endpoint),
+            })
             .header(
                 "User-Agent",
                 "DiscordBot (https://github.com/gaybreak/daybreak 0.1)",
-            )
-            .header("Authorization", &self.token)
-            .body(request_body)?;
+            );
 
-        let response = self.http.request(hyper_request).await?.into_body();
-        let bytes = to_bytes(response).await?;
+        if let Some(token) = &self.token {
+            builder = builder.header("Authorization", token);
+        }
+        if let Some(reason) = &request.reason {
+            builder = builder.header("X-Audit-Log-Reason", reason);
+        }
 
-        Ok(serde_json::from_str(std::str::from_utf8(&bytes)?)?)
+        Ok(match body {
+            Some(body) => builder
+                .header("Content-Type", body.content_type)
+                .body(body.bytes.into())?,
+            None => builder.body(Body::empty())?,
+        })
     }
 }
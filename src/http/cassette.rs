@@ -0,0 +1,268 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use anyhow::{anyhow, Context as _, Error};
+use futures_util::{future::BoxFuture, FutureExt};
+use hyper::{
+    body::to_bytes,
+    header::{HeaderName, HeaderValue, CONTENT_TYPE, SET_COOKIE},
+    http::uri::PathAndQuery,
+    Body, HeaderMap, Request as HyperRequest, Response,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::http::{rate_limit, transport::Transport};
+
+/// A transport that records every request and its response to a JSON
+/// cassette file, or replays them from the file without a network
+///
+/// Record the cassette once with a real token, then commit the file so that
+/// later runs, such as in a sandboxed CI, replay it instead
+///
+/// Requests are matched on their method, path with the query string and
+/// body, each recorded response is replayed once, in the order they were
+/// recorded, so a request sent twice needs to be recorded twice
+///
+/// The request headers aren't recorded and webhook and interaction tokens in
+/// the path are recorded as `:token`, so no token is saved in the cassette
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+///
+/// use daybreak::{http::cassette::Cassette, Context, ContextConfig};
+/// use hyper::Client;
+/// use hyper_rustls::HttpsConnectorBuilder;
+///
+/// let token = std::env::var("BOT_TOKEN").ok();
+/// let cassette = match &token {
+///     Some(_) => Cassette::record(
+///         "tests/cassette.json",
+///         Arc::new(
+///             Client::builder().build(
+///                 HttpsConnectorBuilder::new()
+///                     .with_webpki_roots()
+///                     .https_only()
+///                     .enable_http2()
+///                     .build(),
+///             ),
+///         ),
+///     ),
+///     None => Cassette::replay("tests/cassette.json")?,
+/// };
+///
+/// let ctx = Context::new(&ContextConfig {
+///     token: token.as_deref().unwrap_or_default(),
+///     transport: Some(Arc::new(cassette)),
+///     ..ContextConfig::default()
+/// });
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Cassette {
+    /// The path of the cassette file
+    path: PathBuf,
+    /// The transport to record the responses of, or `None` if replaying
+    transport: Option<Arc<dyn Transport>>,
+    /// The interactions recorded so far, or the ones that are yet to be
+    /// replayed
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+/// A recorded request and its response
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Interaction {
+    /// The method of the request
+    method: String,
+    /// The path of the request, including the query string, with its tokens
+    /// redacted
+    path: String,
+    /// The body of the request, with the multipart boundary replaced
+    body: Option<String>,
+    /// The status code of the response
+    status: u16,
+    /// The headers of the response
+    headers: BTreeMap<String, String>,
+    /// The body of the response
+    response: String,
+}
+
+impl Interaction {
+    /// Whether the interaction was recorded for a request with the given
+    /// method, path and body
+    fn matches(&self, method: &str, path: &str, body: Option<&str>) -> bool {
+        self.method == method && self.path == path && self.body.as_deref() == body
+    }
+}
+
+impl Cassette {
+    /// Create a cassette that sends requests with the given transport,
+    /// overwriting the file at the given path with every interaction
+    pub fn record(path: impl Into<PathBuf>, transport: Arc<dyn Transport>) -> Self {
+        Self {
+            path: path.into(),
+            transport: Some(transport),
+            interactions: Mutex::new(vec![]),
+        }
+    }
+
+    /// Create a cassette that replays the interactions in the file at the
+    /// given path
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a valid cassette
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let interactions = serde_json::from_slice(
+            &fs::read(&path)
+                .with_context(|| format!("can't read the cassette {}", path.display()))?,
+        )?;
+
+        Ok(Self {
+            path,
+            transport: None,
+            interactions: Mutex::new(interactions),
+        })
+    }
+
+    /// Take the first recorded interaction for the request out of the
+    /// cassette
+    fn take(&self, method: &str, path: &str, body: Option<&str>) -> Result<Interaction, Error> {
+        let mut interactions = self
+            .interactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let index = interactions
+            .iter()
+            .position(|interaction| interaction.matches(method, path, body))
+            .ok_or_else(|| {
+                anyhow!(
+                    "no response for {method} {path} is left in the cassette {}",
+                    self.path.display()
+                )
+            })?;
+
+        Ok(interactions.remove(index))
+    }
+
+    /// Add the interaction to the cassette and save it
+    fn push(&self, interaction: Interaction) -> Result<(), Error> {
+        let mut interactions = self
+            .interactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        interactions.push(interaction);
+
+        fs::write(&self.path, serde_json::to_vec_pretty(&*interactions)?)
+            .with_context(|| format!("can't write the cassette {}", self.path.display()))
+    }
+}
+
+impl Transport for Cassette {
+    fn send(&self, request: HyperRequest<Body>) -> BoxFuture<'_, Result<Response<Body>, Error>> {
+        async move {
+            let (parts, body) = request.into_parts();
+            let body = to_bytes(body).await?;
+
+            let method = parts.method.to_string();
+            let path = redact_tokens(
+                parts
+                    .uri
+                    .path_and_query()
+                    .map_or_else(|| parts.uri.path(), PathAndQuery::as_str),
+            );
+            let recorded_body = recorded_body(&parts.headers, &body);
+
+            let Some(transport) = &self.transport else {
+                let interaction = self.take(&method, &path, recorded_body.as_deref())?;
+                return replayed_response(interaction);
+            };
+
+            let response = transport
+                .send(HyperRequest::from_parts(parts, Body::from(body)))
+                .await?;
+            let (parts, body) = response.into_parts();
+            let body = to_bytes(body).await?;
+
+            self.push(Interaction {
+                method,
+                path,
+                body: recorded_body,
+                status: parts.status.as_u16(),
+                headers: parts
+                    .headers
+                    .iter()
+                    .filter(|(name, _)| **name != SET_COOKIE)
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_owned()))
+                    })
+                    .collect(),
+                response: String::from_utf8_lossy(&body).into_owned(),
+            })?;
+
+            Ok(Response::from_parts(parts, Body::from(body)))
+        }
+        .boxed()
+    }
+}
+
+/// The path with its webhook and interaction tokens replaced with `:token`,
+/// keeping the query string
+fn redact_tokens(path: &str) -> String {
+    let (path, query) = path
+        .split_once('?')
+        .map_or((path, None), |(path, query)| (path, Some(query)));
+
+    let mut redacted = rate_limit::segments(path)
+        .map(|segment| match segment.token_prefix {
+            Some(_) => ":token",
+            None => segment.value,
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    if let Some(query) = query {
+        redacted.push('?');
+        redacted.push_str(query);
+    }
+
+    redacted
+}
+
+/// The body of the request as it's recorded, with the random multipart
+/// boundary replaced so that it matches across runs
+fn recorded_body(headers: &HeaderMap, body: &[u8]) -> Option<String> {
+    if body.is_empty() {
+        return None;
+    }
+
+    let body = String::from_utf8_lossy(body);
+    let boundary = headers
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(|content_type| content_type.split_once("boundary="))
+        .map(|(_, boundary)| boundary);
+
+    Some(match boundary {
+        Some(boundary) => body.replace(boundary, "boundary"),
+        None => body.into_owned(),
+    })
+}
+
+/// Build the response of the replayed interaction
+fn replayed_response(interaction: Interaction) -> Result<Response<Body>, Error> {
+    let mut response = Response::builder().status(interaction.status);
+    for (name, value) in &interaction.headers {
+        response = response.header(
+            HeaderName::from_bytes(name.as_bytes())?,
+            HeaderValue::from_str(value)?,
+        );
+    }
+
+    Ok(response.body(Body::from(interaction.response))?)
+}
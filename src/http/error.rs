@@ -0,0 +1,384 @@
+use std::time::Duration;
+
+use enumflags2::BitFlags;
+use hyper::{HeaderMap, StatusCode};
+use serde::Deserialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::{
+    http::{self, rate_limit, Request},
+    model::permission::Permissions,
+    UserError,
+};
+
+/// The scope of a rate limit
+#[doc = discord_url!("https://discord.com/developers/docs/topics/rate-limits#header-format")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitScope {
+    /// The rate limit is per bot or user
+    User,
+    /// The rate limit is global for the bot or user
+    Global,
+    /// The rate limit is shared by everyone using the resource
+    Shared,
+}
+
+impl RateLimitScope {
+    /// Parse the value of the `X-RateLimit-Scope` header
+    pub(crate) fn from_header(value: &str) -> Option<Self> {
+        match value {
+            "user" => Some(Self::User),
+            "global" => Some(Self::Global),
+            "shared" => Some(Self::Shared),
+            _ => None,
+        }
+    }
+}
+
+/// The request was still rate limited after retrying it as many times as
+/// configured in `ContextConfig.max_rate_limit_retries`
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/topics/rate-limits#exceeding-a-rate-limit"
+)]
+#[derive(Error, Clone, Debug)]
+#[error("rate limited on {route} for {retry_after:?} (global: {global}): {message}")]
+pub struct RateLimitError {
+    /// The route of the request, this is the method and the endpoint with the
+    /// parameters other than the major parameter replaced
+    pub route: String,
+    /// How long to wait before making the request again
+    pub retry_after: Duration,
+    /// Whether the rate limit is global
+    pub global: bool,
+    /// The scope of the rate limit, if Discord sent it
+    pub scope: Option<RateLimitScope>,
+    /// The message Discord sent with the rate limit
+    pub message: String,
+}
+
+/// The request didn't complete within its timeout, which is set in
+/// `ContextConfig.timeout` or with [`Request::with_timeout`]
+///
+/// The request is cancelled when this is returned, but it might have reached
+/// Discord already
+///
+/// [`Request::with_timeout`]: crate::http::Request::with_timeout
+#[derive(Error, Clone, Debug)]
+#[error("the request to {route} timed out after {timeout:?}")]
+pub struct TimeoutError {
+    /// The route of the request, this is the method and the endpoint with the
+    /// parameters other than the major parameter replaced
+    pub route: String,
+    /// The timeout that was exceeded
+    pub timeout: Duration,
+}
+
+/// The request was refused because the number of invalid requests in the
+/// last 10 minutes reached `InvalidRequestPolicy.threshold`, to avoid
+/// Cloudflare banning the IP
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/topics/rate-limits\
+    #invalid-request-limit-aka-cloudflare-bans"
+)]
+#[derive(Error, Clone, Copy, Debug)]
+#[error("refused the request after {count} invalid requests, retry after {retry_after:?}")]
+pub struct InvalidRequestLimitError {
+    /// The number of invalid requests in the last 10 minutes
+    pub count: usize,
+    /// How long to wait before the request isn't refused
+    pub retry_after: Duration,
+}
+
+/// The body of the response was bigger than `ContextConfig.max_response_size`
+#[derive(Error, Clone, Copy, Debug)]
+#[error("the response is bigger than the maximum size of {max_size} bytes")]
+pub struct ResponseTooLargeError {
+    /// The maximum size of a response body in bytes
+    pub max_size: usize,
+}
+
+/// The body of a 429 response
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/topics/rate-limits\
+    #exceeding-a-rate-limit-rate-limit-response-structure"
+)]
+#[derive(Deserialize)]
+pub(crate) struct RateLimitResponse {
+    /// A message saying you are being rate limited
+    pub(crate) message: String,
+    /// The number of seconds to wait before submitting another request
+    pub(crate) retry_after: f64,
+    /// Whether the rate limit is global
+    pub(crate) global: bool,
+}
+
+impl RateLimitResponse {
+    /// Parse the body of a `429 Too Many Requests` response, falling back to
+    /// the `Retry-After` or `X-RateLimit-Reset-After` header if the body isn't
+    /// JSON, such as when Cloudflare responds with an HTML page
+    pub(crate) fn new(headers: &HeaderMap, bytes: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(bytes).or_else(|err| {
+            let retry_after = rate_limit::header(headers, "retry-after")
+                .or_else(|| rate_limit::header(headers, "x-ratelimit-reset-after"))
+                .ok_or(err)?;
+
+            Ok(Self {
+                message: "the rate limit response isn't JSON".to_owned(),
+                retry_after,
+                global: rate_limit::header(headers, "x-ratelimit-global").unwrap_or(false),
+            })
+        })
+    }
+}
+
+/// An error response from Discord
+///
+/// Use [`Self::error_code`] to branch on the common error codes
+#[doc = discord_url!("https://discord.com/developers/docs/reference#error-messages")]
+#[derive(Error, Clone, Debug)]
+#[error(
+    "Discord responded with {status}{}: {message}",
+    .code.map_or_else(String::new, |code| format!(", error code {code}"))
+)]
+pub struct DiscordApiError {
+    /// The HTTP status of the response
+    pub status: StatusCode,
+    /// The JSON error code, this is `None` if the response body wasn't a JSON
+    /// error, such as a `502 Bad Gateway` page from Cloudflare
+    pub code: Option<u32>,
+    /// The error message, this is the start of the response body if it
+    /// wasn't a JSON error
+    pub message: String,
+    /// The nested errors, usually of form fields that failed validation
+    pub errors: Option<Value>,
+}
+
+impl DiscordApiError {
+    /// Create the error from the status and body of a response
+    pub(crate) fn new(status: StatusCode, body: &[u8]) -> Self {
+        match serde_json::from_slice::<DiscordApiErrorResponse>(body) {
+            Ok(response) => Self {
+                status,
+                code: Some(response.code),
+                message: response.message,
+                errors: response.errors,
+            },
+            Err(_) => Self {
+                status,
+                code: None,
+                message: http::body_snippet(body),
+                errors: None,
+            },
+        }
+    }
+
+    /// The error code as a known variant, if it's one of the common error codes
+    #[must_use]
+    pub const fn error_code(&self) -> Option<ErrorCode> {
+        match self.code {
+            Some(code) => ErrorCode::from_code(code),
+            None => None,
+        }
+    }
+
+    /// The user-facing error for the error, if it's caused by something the
+    /// user of the bot can fix
+    ///
+    /// `permissions` are the bot's cached permissions for the request, only
+    /// the required permissions it doesn't have are reported as missing, or
+    /// all of them if none are cached or the cache is outdated
+    pub(crate) fn user_error(
+        &self,
+        request: &Request,
+        permissions: Option<BitFlags<Permissions>>,
+    ) -> Option<UserError> {
+        match self.error_code()? {
+            ErrorCode::MissingPermissions => {
+                let missing_permissions = permissions
+                    .map(|permissions| request.required_permissions & !permissions)
+                    .filter(|missing_permissions| !missing_permissions.is_empty())
+                    .unwrap_or(request.required_permissions);
+                Some(UserError::missing_permissions(
+                    missing_permissions,
+                    request.channel_id,
+                ))
+            }
+            ErrorCode::MissingAccess => Some(UserError::MissingAccess),
+            ErrorCode::CannotMessageUser => Some(UserError::CannotMessageUser),
+            _ => None,
+        }
+    }
+}
+
+/// The body of an error response
+#[derive(Deserialize)]
+struct DiscordApiErrorResponse {
+    /// The JSON error code
+    code: u32,
+    /// The error message
+    message: String,
+    /// The nested errors
+    errors: Option<Value>,
+}
+
+/// The common JSON error codes
+#[doc = discord_url!("https://discord.com/developers/docs/topics/opcodes-and-status-codes#json")]
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// A general error, such as one that doesn't have its own code
+    GeneralError = 0,
+    /// The channel doesn't exist
+    UnknownChannel = 10_003,
+    /// The guild doesn't exist
+    UnknownGuild = 10_004,
+    /// The integration doesn't exist
+    UnknownIntegration = 10_005,
+    /// The invite doesn't exist
+    UnknownInvite = 10_006,
+    /// The member doesn't exist
+    UnknownMember = 10_007,
+    /// The message doesn't exist
+    UnknownMessage = 10_008,
+    /// The permission overwrite doesn't exist
+    UnknownOverwrite = 10_009,
+    /// The role doesn't exist
+    UnknownRole = 10_011,
+    /// The token doesn't exist
+    UnknownToken = 10_012,
+    /// The user doesn't exist
+    UnknownUser = 10_013,
+    /// The emoji doesn't exist
+    UnknownEmoji = 10_014,
+    /// The webhook doesn't exist
+    UnknownWebhook = 10_015,
+    /// The ban doesn't exist
+    UnknownBan = 10_026,
+    /// The sticker doesn't exist
+    UnknownSticker = 10_060,
+    /// The interaction doesn't exist, it might have expired
+    UnknownInteraction = 10_062,
+    /// The application command doesn't exist
+    UnknownApplicationCommand = 10_063,
+    /// The scheduled event doesn't exist
+    UnknownScheduledEvent = 10_070,
+    /// Bots can't use this endpoint
+    BotsCannotUseEndpoint = 20_001,
+    /// Only bots can use this endpoint
+    OnlyBotsCanUseEndpoint = 20_002,
+    /// The user is in the maximum number of guilds
+    MaximumGuildsReached = 30_001,
+    /// The channel has the maximum number of pinned messages
+    MaximumPinsReached = 30_003,
+    /// The guild has the maximum number of roles
+    MaximumRolesReached = 30_005,
+    /// The message has the maximum number of reactions
+    MaximumReactionsReached = 30_010,
+    /// The guild has the maximum number of channels
+    MaximumChannelsReached = 30_013,
+    /// The request isn't authorized, provide a valid token
+    Unauthorized = 40_001,
+    /// The request is too large, try sending something smaller
+    RequestTooLarge = 40_005,
+    /// The interaction was already responded to
+    InteractionAlreadyAcknowledged = 40_060,
+    /// The bot can't access the resource, such as a channel it can't view
+    MissingAccess = 50_001,
+    /// The account type can't use the endpoint
+    InvalidAccountType = 50_002,
+    /// The action can't be done in a DM channel
+    CannotExecuteOnDmChannel = 50_003,
+    /// Messages sent by other users can't be edited
+    CannotEditOtherUsersMessage = 50_005,
+    /// The message has no content, embeds, files or stickers
+    CannotSendEmptyMessage = 50_006,
+    /// The user doesn't accept DMs from the bot
+    CannotMessageUser = 50_007,
+    /// Messages can't be sent in a channel that isn't a text channel
+    CannotSendMessagesInNonTextChannel = 50_008,
+    /// The OAuth2 state is invalid
+    InvalidOAuth2State = 50_012,
+    /// The bot doesn't have the permissions the request requires
+    MissingPermissions = 50_013,
+    /// The authentication token is invalid
+    InvalidToken = 50_014,
+    /// The note is too long
+    NoteTooLong = 50_015,
+    /// Too few or too many messages were given to delete
+    InvalidMessageDeleteCount = 50_016,
+    /// The action can't be done on a system message
+    CannotExecuteOnSystemMessage = 50_021,
+    /// A message is too old to be bulk deleted
+    MessageTooOldToBulkDelete = 50_034,
+    /// The body of the request is invalid, refer to `errors` for the fields
+    InvalidFormBody = 50_035,
+    /// An uploaded file is invalid
+    InvalidFileUploaded = 50_046,
+    /// The action can't be done in an archived thread
+    ThreadArchived = 50_083,
+    /// The action requires the bot's owner to have two-factor authentication
+    TwoFactorRequired = 60_003,
+    /// The user blocked the bot from reacting to their messages
+    ReactionBlocked = 90_001,
+    /// Discord is overloaded, try again later
+    ResourceOverloaded = 130_000,
+}
+
+impl ErrorCode {
+    /// Get the known variant of the given error code
+    #[must_use]
+    pub const fn from_code(code: u32) -> Option<Self> {
+        Some(match code {
+            0 => Self::GeneralError,
+            10_003 => Self::UnknownChannel,
+            10_004 => Self::UnknownGuild,
+            10_005 => Self::UnknownIntegration,
+            10_006 => Self::UnknownInvite,
+            10_007 => Self::UnknownMember,
+            10_008 => Self::UnknownMessage,
+            10_009 => Self::UnknownOverwrite,
+            10_011 => Self::UnknownRole,
+            10_012 => Self::UnknownToken,
+            10_013 => Self::UnknownUser,
+            10_014 => Self::UnknownEmoji,
+            10_015 => Self::UnknownWebhook,
+            10_026 => Self::UnknownBan,
+            10_060 => Self::UnknownSticker,
+            10_062 => Self::UnknownInteraction,
+            10_063 => Self::UnknownApplicationCommand,
+            10_070 => Self::UnknownScheduledEvent,
+            20_001 => Self::BotsCannotUseEndpoint,
+            20_002 => Self::OnlyBotsCanUseEndpoint,
+            30_001 => Self::MaximumGuildsReached,
+            30_003 => Self::MaximumPinsReached,
+            30_005 => Self::MaximumRolesReached,
+            30_010 => Self::MaximumReactionsReached,
+            30_013 => Self::MaximumChannelsReached,
+            40_001 => Self::Unauthorized,
+            40_005 => Self::RequestTooLarge,
+            40_060 => Self::InteractionAlreadyAcknowledged,
+            50_001 => Self::MissingAccess,
+            50_002 => Self::InvalidAccountType,
+            50_003 => Self::CannotExecuteOnDmChannel,
+            50_005 => Self::CannotEditOtherUsersMessage,
+            50_006 => Self::CannotSendEmptyMessage,
+            50_007 => Self::CannotMessageUser,
+            50_008 => Self::CannotSendMessagesInNonTextChannel,
+            50_012 => Self::InvalidOAuth2State,
+            50_013 => Self::MissingPermissions,
+            50_014 => Self::InvalidToken,
+            50_015 => Self::NoteTooLong,
+            50_016 => Self::InvalidMessageDeleteCount,
+            50_021 => Self::CannotExecuteOnSystemMessage,
+            50_034 => Self::MessageTooOldToBulkDelete,
+            50_035 => Self::InvalidFormBody,
+            50_046 => Self::InvalidFileUploaded,
+            50_083 => Self::ThreadArchived,
+            60_003 => Self::TwoFactorRequired,
+            90_001 => Self::ReactionBlocked,
+            130_000 => Self::ResourceOverloaded,
+            _ => return None,
+        })
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    collections::VecDeque,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use hyper::StatusCode;
+
+use crate::{http::error::RateLimitScope, Context};
+
+/// How long Discord counts invalid requests for
+const WINDOW: Duration = Duration::from_secs(600);
+
+/// What to do with a request when the invalid requests reach the threshold
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidRequestAction {
+    /// Return an [`InvalidRequestLimitError`] without sending the request
+    ///
+    /// [`InvalidRequestLimitError`]: crate::http::error::InvalidRequestLimitError
+    Refuse,
+    /// Wait until enough of the invalid requests are out of the window, then
+    /// send the request
+    ///
+    /// The count is checked again after waiting, since other requests might
+    /// have been waiting for the same invalid requests to leave the window
+    Delay,
+}
+
+/// How to guard against the ban Cloudflare puts on IPs that make 10,000
+/// invalid requests in 10 minutes
+///
+/// Responses with `401 Unauthorized`, `403 Forbidden` or
+/// `429 Too Many Requests` are invalid, except for 429s with the `shared`
+/// scope, they're counted in a sliding window of 10 minutes, shared by every
+/// clone of a context
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/topics/rate-limits\
+    #invalid-request-limit-aka-cloudflare-bans"
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidRequestPolicy {
+    /// The number of invalid requests in the window to start refusing or
+    /// delaying requests at, set this above 10,000 to disable it
+    pub threshold: u16,
+    /// What to do with requests after reaching the threshold
+    pub action: InvalidRequestAction,
+}
+
+impl Default for InvalidRequestPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: 9000,
+            action: InvalidRequestAction::Refuse,
+        }
+    }
+}
+
+/// The invalid requests made in the window, shared by every clone of a
+/// context
+#[derive(Debug, Default)]
+pub(crate) struct InvalidRequests {
+    /// When each invalid response in the window was received, the oldest
+    /// first
+    received_at: Mutex<VecDeque<Instant>>,
+}
+
+impl InvalidRequests {
+    /// Count the response if it's invalid
+    pub(crate) fn record(&self, status: StatusCode, scope: Option<RateLimitScope>) {
+        let is_invalid = status == StatusCode::UNAUTHORIZED
+            || status == StatusCode::FORBIDDEN
+            || (status == StatusCode::TOO_MANY_REQUESTS && scope != Some(RateLimitScope::Shared));
+
+        if is_invalid {
+            self.pruned().push_back(Instant::now());
+        }
+    }
+
+    /// The number of invalid requests in the window
+    pub(crate) fn count(&self) -> usize {
+        self.pruned().len()
+    }
+
+    /// How long until there are fewer invalid requests in the window than
+    /// the threshold, or `None` if there already are
+    pub(crate) fn wait_time(&self, threshold: u16) -> Option<Duration> {
+        let received_at = self.pruned();
+        let excess = received_at.len().checked_sub(usize::from(threshold))?;
+
+        received_at
+            .get(excess)?
+            .checked_add(WINDOW)?
+            .checked_duration_since(Instant::now())
+    }
+
+    /// Lock the invalid requests, removing the ones that are out of the
+    /// window, they're the oldest so only the front is checked
+    fn pruned(&self) -> MutexGuard<'_, VecDeque<Instant>> {
+        let mut received_at = self
+            .received_at
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let expired = received_at.partition_point(|at| at.elapsed() >= WINDOW);
+        received_at.drain(..expired).for_each(drop);
+
+        received_at
+    }
+}
+
+impl Context {
+    /// The number of invalid requests made with the context in the last 10
+    /// minutes, refer to [`InvalidRequestPolicy`]
+    ///
+    /// This is useful to alert before Cloudflare bans the IP
+    #[must_use]
+    pub fn invalid_request_count(&self) -> usize {
+        self.invalid_requests.count()
+    }
+}
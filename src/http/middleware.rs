@@ -0,0 +1,86 @@
+use std::{fmt::Debug, time::Duration};
+
+use anyhow::Error;
+use hyper::{
+    header::{HeaderValue, AUTHORIZATION},
+    Body, HeaderMap, Request as HyperRequest, StatusCode,
+};
+
+use crate::http::Request;
+
+/// Hooks called around every request made with a context, such as to trace
+/// requests or collect metrics
+///
+/// Every method does nothing by default, register the middleware in
+/// `ContextConfig.middleware`, they're called in the order they're given
+///
+/// The hooks are called for every attempt of a request, after the request
+/// waits for the rate limiter, so the latency doesn't include the time spent
+/// waiting
+///
+/// # Example
+///
+/// ```rust
+/// use std::{
+///     collections::HashMap,
+///     sync::{Arc, Mutex},
+/// };
+///
+/// use daybreak::{
+///     http::{
+///         middleware::{Middleware, ResponseInfo},
+///         Request,
+///     },
+///     Context, ContextConfig,
+/// };
+///
+/// #[derive(Debug, Default)]
+/// struct RequestCounter(Mutex<HashMap<String, u32>>);
+///
+/// impl Middleware for RequestCounter {
+///     fn on_response(&self, request: &Request, response: &ResponseInfo<'_>) {
+///         *self.0.lock().unwrap().entry(request.route()).or_default() += 1;
+///         println!("{} took {:?}", request.route(), response.latency);
+///     }
+/// }
+///
+/// let ctx = Context::new(&ContextConfig {
+///     token: "my totally real token",
+///     middleware: vec![Arc::new(RequestCounter::default())],
+///     ..ContextConfig::default()
+/// });
+/// ```
+pub trait Middleware: Debug + Send + Sync {
+    /// Called before a request is sent, the built request can be modified,
+    /// such as to add headers
+    fn on_request(&self, _request: &Request, _hyper_request: &mut HyperRequest<Body>) {}
+
+    /// Called after a response is received, this is called for error
+    /// responses too
+    fn on_response(&self, _request: &Request, _response: &ResponseInfo<'_>) {}
+
+    /// Called when sending a request fails, without a response
+    fn on_error(&self, _request: &Request, _error: &Error, _latency: Duration) {}
+}
+
+/// Info about a response to a request
+#[derive(Clone, Copy, Debug)]
+pub struct ResponseInfo<'res> {
+    /// The status of the response
+    pub status: StatusCode,
+    /// The headers of the response
+    pub headers: &'res HeaderMap,
+    /// How long it took to receive the response after sending the request
+    pub latency: Duration,
+}
+
+/// Copy the headers, replacing the value of the `Authorization` header, so
+/// that they're safe to log
+#[must_use]
+pub fn redacted_headers(headers: &HeaderMap) -> HeaderMap {
+    let mut redacted = headers.clone();
+    if let Some(authorization) = redacted.get_mut(AUTHORIZATION) {
+        *authorization = HeaderValue::from_static("[redacted]");
+    }
+    redacted
+}
@@ -0,0 +1,106 @@
+use anyhow::{bail, Error};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::http::{random, File};
+
+/// An item of the `attachments` array of the payload
+#[derive(Serialize)]
+struct Attachment<'file> {
+    /// The index of the file's `files[n]` part
+    id: usize,
+    /// The name of the file
+    filename: &'file str,
+    /// The description of the file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'file str>,
+}
+
+/// Create a random boundary to separate the parts of a form
+pub(crate) fn boundary() -> String {
+    format!("daybreak-{:016x}", random())
+}
+
+/// Encode the params and the files as a `multipart/form-data` body with the
+/// given boundary
+///
+/// The params are sent in the `payload_json` part, with the files added to
+/// its `attachments` array, referencing the `files[n]` parts by their index
+#[doc = discord_url!("https://discord.com/developers/docs/reference#uploading-files")]
+pub(crate) fn encode(
+    params: Option<impl Serialize>,
+    files: &[File],
+    boundary: &str,
+) -> Result<Vec<u8>, Error> {
+    let mut payload = match params.map(|p| serde_json::to_value(p)).transpose()? {
+        Some(Value::Object(map)) => map,
+        None => Map::new(),
+        Some(_) => bail!("the params of a request with files must be a JSON object"),
+    };
+
+    let attachments = files
+        .iter()
+        .enumerate()
+        .map(|(id, file)| {
+            serde_json::to_value(Attachment {
+                id,
+                filename: &file.filename,
+                description: file.description.as_deref(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match payload
+        .entry("attachments")
+        .or_insert_with(|| Value::Array(vec![]))
+    {
+        Value::Array(existing) => existing.extend(attachments),
+        _ => bail!("the `attachments` param of a request with files must be an array"),
+    }
+
+    let mut body = vec![];
+    push_part_headers(
+        &mut body,
+        boundary,
+        "name=\"payload_json\"",
+        "application/json",
+    );
+    body.extend(serde_json::to_vec(&payload)?);
+    body.extend(b"\r\n");
+
+    for (id, file) in files.iter().enumerate() {
+        push_part_headers(
+            &mut body,
+            boundary,
+            &format!(
+                "name=\"files[{id}]\"; filename=\"{}\"",
+                escape_filename(&file.filename)
+            ),
+            &file.content_type,
+        );
+        body.extend(&file.bytes);
+        body.extend(b"\r\n");
+    }
+
+    body.extend(format!("--{boundary}--\r\n").into_bytes());
+
+    Ok(body)
+}
+
+/// Push the boundary and the headers of a part
+fn push_part_headers(body: &mut Vec<u8>, boundary: &str, disposition: &str, content_type: &str) {
+    body.extend(
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; {disposition}\r\nContent-Type: \
+             {content_type}\r\n\r\n"
+        )
+        .into_bytes(),
+    );
+}
+
+/// Escape the characters that would break the `Content-Disposition` header
+fn escape_filename(filename: &str) -> String {
+    filename
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
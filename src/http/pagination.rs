@@ -0,0 +1,79 @@
+use anyhow::Error;
+use futures_util::{
+    stream::{self, BoxStream},
+    StreamExt, TryStreamExt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{http::Request, model::Id, Context};
+
+/// The direction to walk a paginated resource in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Direction {
+    /// Walk from the newest to the oldest items, using the `before` cursor
+    Before,
+    /// Walk from the oldest to the newest items, using the `after` cursor
+    After,
+}
+
+/// The query string of a page
+#[derive(Serialize)]
+struct Page {
+    /// Get the items before this ID
+    before: Option<Id>,
+    /// Get the items after this ID
+    after: Option<Id>,
+    /// The maximum number of items in the page
+    limit: u16,
+}
+
+impl Context {
+    /// Walk every page of the given request, yielding each item
+    ///
+    /// The next page is requested using the smallest or the biggest ID in the
+    /// previous page as the cursor, depending on the direction, and the walk
+    /// ends when a page has fewer items than the limit
+    ///
+    /// Each page is a normal request, so it waits for the rate limiter
+    pub(crate) fn paginate<'ctx, T: DeserializeOwned + Send + 'ctx>(
+        &'ctx self,
+        request: Request,
+        direction: Direction,
+        limit: u16,
+        id: fn(&T) -> Option<Id>,
+    ) -> BoxStream<'ctx, Result<T, Error>> {
+        stream::try_unfold(Some(None), move |cursor: Option<Option<Id>>| {
+            let request = request.clone();
+            async move {
+                let cursor = match cursor {
+                    Some(cursor) => cursor,
+                    None => return Ok(None),
+                };
+
+                let page: Vec<T> = self
+                    .empty_request(request.with_query(&Page {
+                        before: cursor.filter(|_| direction == Direction::Before),
+                        after: cursor.filter(|_| direction == Direction::After),
+                        limit,
+                    })?)
+                    .await?;
+
+                let next_cursor = if page.len() < usize::from(limit) {
+                    None
+                } else {
+                    let ids = page.iter().filter_map(id);
+                    match direction {
+                        Direction::Before => ids.min(),
+                        Direction::After => ids.max(),
+                    }
+                    .map(Some)
+                };
+
+                Ok::<_, Error>(Some((page, next_cursor)))
+            }
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+}
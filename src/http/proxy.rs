@@ -0,0 +1,209 @@
+use std::{convert::Infallible, net::SocketAddr};
+
+use anyhow::Error;
+use enumflags2::BitFlags;
+use hyper::{
+    body::to_bytes,
+    header::{HeaderValue, CONTENT_TYPE},
+    http::{request::Parts, uri::PathAndQuery},
+    service::{make_service_fn, service_fn},
+    Body, Request as HyperRequest, Response, Server, StatusCode,
+};
+use serde_json::json;
+use tokio::time::timeout;
+
+use crate::{
+    http::{
+        error::{RateLimitError, TimeoutError},
+        Request,
+    },
+    Context, ContextConfig,
+};
+
+/// The headers that only apply to a single connection, so they aren't
+/// forwarded
+const HOP_BY_HOP_HEADERS: [&str; 6] = [
+    "connection",
+    "host",
+    "keep-alive",
+    "te",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// A proxy that forwards requests to Discord, waiting for the rate limiter
+/// and retrying them the same way a context does
+///
+/// Run one proxy and set `ContextConfig.proxy_url` in every process of the
+/// bot so that they share the same rate limit buckets, the
+/// `daybreak-proxy` binary runs this, it's behind the `proxy` feature
+///
+/// The requests are forwarded as they are, including their `Authorization`
+/// header, so the proxy doesn't need a token, but its buckets are shared by
+/// every token it receives, so a proxy should be used by a single bot
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use daybreak::{http::proxy::Proxy, ContextConfig};
+///
+/// # async fn run() -> Result<(), anyhow::Error> {
+/// Proxy::new("https://discord.com", &ContextConfig::default())
+///     .serve(([127, 0, 0, 1], 8080).into(), |err| {
+///         eprintln!("forwarding a request failed: {err:?}");
+///     })
+///     .await?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Proxy {
+    /// The context whose rate limiter, retry policy, middleware and transport
+    /// are used, its token isn't
+    ctx: Context,
+    /// The URL requests are forwarded to, without a trailing slash
+    upstream: String,
+}
+
+impl Proxy {
+    /// Create a proxy that forwards requests to the given URL, such as
+    /// `https://discord.com`, with a context created from the config
+    ///
+    /// The `token`, `api_url`, `api_version` and `proxy_url` fields of the
+    /// config are ignored
+    #[must_use]
+    pub fn new(upstream: &str, config: &ContextConfig<'_>) -> Self {
+        Self {
+            ctx: Context::new(&ContextConfig {
+                proxy_url: None,
+                ..config.clone()
+            }),
+            upstream: upstream.to_owned(),
+        }
+    }
+
+    /// Accept requests on the given address until the server fails
+    ///
+    /// Requests that can't be forwarded are answered with
+    /// [`Self::error_response`] and passed to `on_error`, such as to log
+    /// them
+    ///
+    /// The requests have to use HTTP/2 over plain HTTP, which is what
+    /// contexts use when `ContextConfig.proxy_url` is set
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address can't be bound or the server fails
+    pub async fn serve(self, addr: SocketAddr, on_error: fn(&Error)) -> Result<(), Error> {
+        let make_service = make_service_fn(move |_| {
+            let proxy = self.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let proxy = proxy.clone();
+                    async move {
+                        Ok::<_, Infallible>(proxy.forward(request).await.unwrap_or_else(|err| {
+                            on_error(&err);
+                            Self::error_response(&err)
+                        }))
+                    }
+                }))
+            }
+        });
+
+        Server::try_bind(&addr)?
+            .http2_only(true)
+            .serve(make_service)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Forward the request to the upstream URL, returning its response
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RateLimitError`] if the request was still rate limited
+    /// after retrying it, a [`TimeoutError`] if it didn't complete within
+    /// `ContextConfig.timeout`, or the error of the last attempt if it
+    /// couldn't be sent, use [`Self::error_response`] to respond with it
+    pub async fn forward(&self, request: HyperRequest<Body>) -> Result<Response<Body>, Error> {
+        let (parts, body) = request.into_parts();
+        let request = Request::new(
+            BitFlags::empty(),
+            parts.method.clone(),
+            parts
+                .uri
+                .path_and_query()
+                .map_or("/", PathAndQuery::as_str)
+                .to_owned(),
+        );
+
+        match self.ctx.timeout {
+            Some(duration) => timeout(duration, self.try_forward(&request, parts, body))
+                .await
+                .map_err(|_| TimeoutError {
+                    route: request.route(),
+                    timeout: duration,
+                })?,
+            None => self.try_forward(&request, parts, body).await,
+        }
+    }
+
+    /// The response to return when forwarding a request failed
+    ///
+    /// This is a `429 Too Many Requests` for a [`RateLimitError`], a
+    /// `504 Gateway Timeout` for a [`TimeoutError`] or a `502 Bad Gateway`
+    /// otherwise, with a JSON body like Discord's, only the top-level message
+    /// of the error is included
+    #[must_use]
+    pub fn error_response(err: &Error) -> Response<Body> {
+        let (status, body) = match err.downcast_ref::<RateLimitError>() {
+            Some(rate_limit) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                json!({
+                    "message": rate_limit.message,
+                    "retry_after": rate_limit.retry_after.as_secs_f64(),
+                    "global": rate_limit.global,
+                }),
+            ),
+            None if err.is::<TimeoutError>() => (
+                StatusCode::GATEWAY_TIMEOUT,
+                json!({ "message": err.to_string(), "code": 0_u32 }),
+            ),
+            None => (
+                StatusCode::BAD_GATEWAY,
+                json!({ "message": err.to_string(), "code": 0_u32 }),
+            ),
+        };
+
+        let mut response = Response::new(Body::from(body.to_string()));
+        *response.status_mut() = status;
+        response
+            .headers_mut()
+            .extend([(CONTENT_TYPE, HeaderValue::from_static("application/json"))]);
+        response
+    }
+
+    /// Forward the request, returning an error if it fails
+    async fn try_forward(
+        &self,
+        request: &Request,
+        parts: Parts,
+        body: Body,
+    ) -> Result<Response<Body>, Error> {
+        let body = to_bytes(body).await?;
+
+        self.ctx
+            .send(request, || {
+                let mut builder = HyperRequest::builder()
+                    .method(parts.method.clone())
+                    .uri(format!("{}{}", self.upstream, request.endpoint));
+                for (name, value) in &parts.headers {
+                    if !HOP_BY_HOP_HEADERS.contains(&name.as_str()) {
+                        builder = builder.header(name, value);
+                    }
+                }
+                Ok(builder.body(Body::from(body.clone()))?)
+            })
+            .await
+    }
+}
@@ -0,0 +1,417 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt::Display,
+    hash::{Hash, Hasher},
+    str::FromStr,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use hyper::{HeaderMap, Method};
+use tokio::{
+    sync::{Mutex as AsyncMutex, OwnedMutexGuard},
+    time::sleep,
+};
+
+use crate::Context;
+
+/// The path segments that are followed by a major parameter
+const MAJOR_PARAMETER_PREFIXES: [&str; 3] = ["channels", "guilds", "webhooks"];
+
+/// The path segments that are followed by an ID and a token
+const TOKEN_PREFIXES: [&str; 2] = ["interactions", "webhooks"];
+
+/// The rate limit route of a request, requests with the same route share a
+/// bucket
+///
+/// This is the method and the endpoint with every parameter other than the
+/// major parameter replaced, so `GET /channels/1/messages/2` and `GET
+/// /channels/1/messages/3` share a bucket but `GET /channels/4/messages/2`
+/// doesn't
+///
+/// Webhook and interaction tokens are shown as `:token` so the route can be
+/// logged, the hash of a webhook token is kept instead since it's part of the
+/// major parameter
+#[doc = discord_url!("https://discord.com/developers/docs/topics/rate-limits#rate-limits")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct Route {
+    /// The method and the endpoint with the parameters replaced
+    route: String,
+    /// The hash of the webhook token in the endpoint, if there's one
+    token_hash: Option<u64>,
+}
+
+impl Route {
+    /// Create the route of a request from its method and endpoint
+    pub(crate) fn new(method: &Method, endpoint: &str) -> Self {
+        let path = endpoint.split('?').next().unwrap_or_default();
+        let mut route = method.to_string();
+        route.push(' ');
+
+        let mut token_hash = None;
+        for segment in segments(path).filter(|segment| !segment.value.is_empty()) {
+            route.push('/');
+            if let Some(prefix) = segment.token_prefix {
+                route.push_str(":token");
+                if prefix == "webhooks" {
+                    let mut hasher = DefaultHasher::new();
+                    segment.value.hash(&mut hasher);
+                    token_hash = Some(hasher.finish());
+                }
+            } else if MAJOR_PARAMETER_PREFIXES.contains(&segment.previous) {
+                route.push_str(segment.value);
+            } else if segment.previous == "reactions" {
+                route.push_str(":emoji");
+            } else if is_id(segment.value) {
+                route.push_str(":id");
+            } else {
+                route.push_str(segment.value);
+            }
+        }
+
+        Self { route, token_hash }
+    }
+}
+
+impl Display for Route {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.route)
+    }
+}
+
+/// A segment of a path
+pub(crate) struct Segment<'path> {
+    /// The segment before this one, empty for the first segment
+    pub(crate) previous: &'path str,
+    /// The segment itself
+    pub(crate) value: &'path str,
+    /// The segment before the ID this token follows, if this is a webhook or
+    /// interaction token
+    pub(crate) token_prefix: Option<&'path str>,
+}
+
+/// Walk the segments of the path, which are those of `path.split('/')`, so
+/// the first one is empty if the path starts with a slash
+pub(crate) fn segments(path: &str) -> impl Iterator<Item = Segment<'_>> {
+    let mut previous_segments = ["", ""];
+    path.split('/').map(move |value| {
+        let [before_previous, previous] = previous_segments;
+        previous_segments = [previous, value];
+        Segment {
+            previous,
+            value,
+            token_prefix: (TOKEN_PREFIXES.contains(&before_previous) && is_id(previous))
+                .then_some(before_previous),
+        }
+    })
+}
+
+/// Whether the given path segment is a snowflake
+fn is_id(segment: &str) -> bool {
+    !segment.is_empty() && segment.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Keeps track of the rate limit buckets, queueing requests so they never go
+/// over the limit
+///
+/// This is shared by every clone of a context
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    /// The buckets of every route a request was made to
+    buckets: Mutex<HashMap<Route, Arc<Bucket>>>,
+    /// When the global rate limit resets, if it's been hit
+    global_reset_at: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    /// Wait until a request can be made to the given route
+    ///
+    /// Requests to the same route are queued in the order this is called, the
+    /// returned ticket has to be updated with the response headers, the next
+    /// request in the queue is let through when it's dropped
+    ///
+    /// Dropping the future while it's waiting, such as when the request times
+    /// out, leaves the queue without using a request, if the request is
+    /// cancelled after this returns, it still counts against the bucket until
+    /// it resets, since it might have reached Discord
+    pub(crate) async fn acquire(&self, route: Route) -> Ticket {
+        let bucket = Arc::clone(
+            self.buckets
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(route)
+                .or_default(),
+        );
+
+        let queued = Queued::new(&bucket.queued);
+        let guard = Arc::clone(&bucket.queue).lock_owned().await;
+        self.wait_global().await;
+        let wait_time = bucket.state().wait_time();
+        if let Some(wait) = wait_time {
+            sleep(wait).await;
+        }
+        bucket.state().use_request();
+        drop(queued);
+
+        Ticket {
+            bucket,
+            _guard: guard,
+        }
+    }
+
+    /// Pause every request for the given duration, used when the global rate
+    /// limit is hit
+    pub(crate) fn pause_global(&self, retry_after: Duration) {
+        *self.global_reset() = Instant::now().checked_add(retry_after);
+    }
+
+    /// Wait until the global rate limit resets, if it's been hit
+    async fn wait_global(&self) {
+        loop {
+            let wait_time = self
+                .global_reset()
+                .and_then(|reset_at| reset_at.checked_duration_since(Instant::now()));
+            match wait_time {
+                Some(wait) => sleep(wait).await,
+                None => return,
+            }
+        }
+    }
+
+    /// Lock the global reset time
+    fn global_reset(&self) -> MutexGuard<'_, Option<Instant>> {
+        self.global_reset_at
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The current state of the global rate limit and every bucket
+    pub(crate) fn info(&self) -> RateLimitInfo {
+        let now = Instant::now();
+        let global_reset_after = self
+            .global_reset()
+            .and_then(|reset_at| reset_at.checked_duration_since(now));
+
+        let mut buckets: Vec<_> = self
+            .buckets
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(route, bucket)| bucket.info(route, now))
+            .collect();
+        buckets.sort_unstable_by(|first, second| first.route.cmp(&second.route));
+
+        RateLimitInfo {
+            global_reset_after,
+            buckets,
+        }
+    }
+}
+
+/// A rate limit bucket, made of a queue of requests and the last known state
+#[derive(Debug, Default)]
+struct Bucket {
+    /// Locked while a request to the bucket is in flight
+    queue: Arc<AsyncMutex<()>>,
+    /// The state of the bucket as Discord last reported it
+    state: Mutex<BucketState>,
+    /// The number of requests waiting for their turn in the bucket
+    queued: Mutex<usize>,
+}
+
+impl Bucket {
+    /// Lock the state of the bucket
+    fn state(&self) -> MutexGuard<'_, BucketState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The current state of the bucket, as of `now`
+    fn info(&self, route: &Route, now: Instant) -> BucketInfo {
+        let state = self.state();
+        let reset_after = state
+            .reset_at
+            .and_then(|reset_at| reset_at.checked_duration_since(now));
+
+        BucketInfo {
+            route: route.to_string(),
+            hash: state.hash.clone(),
+            limit: state.limit,
+            remaining: if reset_after.is_some() {
+                state.remaining
+            } else {
+                state.limit
+            },
+            reset_after,
+            queued: *self.queued.lock().unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+}
+
+/// Counts a request as queued in a bucket until it's dropped, so that
+/// requests whose future is dropped while waiting stop being counted
+struct Queued<'bucket>(&'bucket Mutex<usize>);
+
+impl<'bucket> Queued<'bucket> {
+    /// Count a request as queued
+    fn new(queued: &'bucket Mutex<usize>) -> Self {
+        let mut count = queued.lock().unwrap_or_else(PoisonError::into_inner);
+        *count = count.saturating_add(1);
+        drop(count);
+        Self(queued)
+    }
+}
+
+impl Drop for Queued<'_> {
+    fn drop(&mut self) {
+        let mut count = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        *count = count.saturating_sub(1);
+    }
+}
+
+/// The state of a rate limit bucket as reported in the response headers
+#[derive(Debug, Default)]
+struct BucketState {
+    /// The unique hash of the bucket, different routes may share the same
+    /// hash
+    hash: Option<String>,
+    /// The number of requests that can be made before the bucket resets
+    limit: Option<u16>,
+    /// The number of requests left before the bucket resets
+    remaining: Option<u16>,
+    /// When the bucket resets
+    reset_at: Option<Instant>,
+}
+
+impl BucketState {
+    /// How long to wait before making a request, if the bucket is exhausted
+    fn wait_time(&self) -> Option<Duration> {
+        if self.remaining != Some(0) {
+            return None;
+        }
+        self.reset_at
+            .and_then(|reset_at| reset_at.checked_duration_since(Instant::now()))
+    }
+
+    /// Mark one of the remaining requests as used, forgetting the state if
+    /// the bucket has reset
+    fn use_request(&mut self) {
+        if self
+            .reset_at
+            .map_or(false, |reset_at| reset_at <= Instant::now())
+        {
+            self.remaining = self.limit;
+            self.reset_at = None;
+        }
+        self.remaining = self.remaining.map(|remaining| remaining.saturating_sub(1));
+    }
+
+    /// Update the state from the headers of a response
+    fn update(&mut self, headers: &HeaderMap) {
+        if let Some(hash) = header(headers, "x-ratelimit-bucket") {
+            self.hash = Some(hash);
+        }
+        if let Some(limit) = header(headers, "x-ratelimit-limit") {
+            self.limit = Some(limit);
+        }
+        if let Some(remaining) = header(headers, "x-ratelimit-remaining") {
+            self.remaining = Some(remaining);
+        }
+        if let Some(reset_after) = header::<f64>(headers, "x-ratelimit-reset-after") {
+            self.reset_at = Duration::try_from_secs_f64(reset_after)
+                .ok()
+                .and_then(|reset_after| Instant::now().checked_add(reset_after));
+        }
+    }
+}
+
+/// Permission to make a request to a bucket, the next request in the queue
+/// is let through when this is dropped
+#[derive(Debug)]
+pub(crate) struct Ticket {
+    /// The bucket the request is made to
+    bucket: Arc<Bucket>,
+    /// Keeps the other requests to the bucket queued
+    _guard: OwnedMutexGuard<()>,
+}
+
+impl Ticket {
+    /// Update the bucket with the headers of the response
+    pub(crate) fn update(&self, headers: &HeaderMap) {
+        self.bucket.state().update(headers);
+    }
+
+    /// Pause the requests to the bucket for the given duration, used when the
+    /// bucket's rate limit is hit
+    pub(crate) fn pause(&self, retry_after: Duration) {
+        let mut state = self.bucket.state();
+        state.remaining = Some(0);
+        state.reset_at = Instant::now().checked_add(retry_after);
+    }
+}
+
+/// Parse the value of the header with the given name
+pub(crate) fn header<T: FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// A snapshot of the rate limits of a context, returned by
+/// [`Context::rate_limits`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// How long until the global rate limit resets, if it's been hit
+    pub global_reset_after: Option<Duration>,
+    /// Every bucket a request was made to, sorted by their route
+    pub buckets: Vec<BucketInfo>,
+}
+
+impl RateLimitInfo {
+    /// The buckets that have no requests left until they reset
+    pub fn exhausted(&self) -> impl Iterator<Item = &BucketInfo> {
+        self.buckets.iter().filter(|bucket| bucket.is_exhausted())
+    }
+}
+
+/// A snapshot of a rate limit bucket
+///
+/// The state is what Discord reported in the headers of the last response,
+/// so the fields are `None` until a response with the headers is received
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BucketInfo {
+    /// The route of the bucket, which is the method and the endpoint with
+    /// every parameter other than the major parameter replaced, such as
+    /// `POST /channels/1234/messages`
+    pub route: String,
+    /// The unique hash of the bucket, different routes may share the same
+    /// hash
+    pub hash: Option<String>,
+    /// The number of requests that can be made before the bucket resets
+    pub limit: Option<u16>,
+    /// The number of requests left before the bucket resets
+    pub remaining: Option<u16>,
+    /// How long until the bucket resets, `None` if it already has
+    pub reset_after: Option<Duration>,
+    /// The number of requests waiting for their turn in the bucket, not
+    /// counting the one that's being made
+    pub queued: usize,
+}
+
+impl BucketInfo {
+    /// Whether the bucket has no requests left until it resets, so the next
+    /// request waits for `reset_after`
+    #[must_use]
+    pub const fn is_exhausted(&self) -> bool {
+        matches!(self.remaining, Some(0)) && self.reset_after.is_some()
+    }
+}
+
+impl Context {
+    /// The current state of the rate limits of the context, which is shared
+    /// by its clones
+    ///
+    /// Returns `None` if `ContextConfig.proxy_url` is set, since the proxy
+    /// handles the rate limits then
+    #[must_use]
+    pub fn rate_limits(&self) -> Option<RateLimitInfo> {
+        self.rate_limiter.as_ref().map(|limiter| limiter.info())
+    }
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use hyper::Method;
+
+use crate::http::random;
+
+/// How to retry requests that failed because of a transient error, such as a
+/// `502 Bad Gateway` or a dropped connection
+///
+/// Rate limited requests are retried separately, refer to
+/// `ContextConfig.max_rate_limit_retries`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of times to send a request, including the first
+    /// attempt, set this to `1` to disable retries
+    pub max_attempts: u8,
+    /// The delay before the first retry, it's doubled for every retry after
+    /// that
+    pub base_delay: Duration,
+    /// The maximum delay between retries
+    pub max_delay: Duration,
+    /// Whether to retry requests that aren't idempotent, such as `POST`, when
+    /// they might have reached Discord
+    ///
+    /// This is `false` by default, since retrying them might, for example,
+    /// send a message twice, requests that never reached Discord because the
+    /// connection failed are always retried
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait before retrying a request that failed on the given
+    /// attempt, or `None` if it shouldn't be retried
+    ///
+    /// `might_have_reached_discord` is `false` when it's known that Discord
+    /// didn't receive the request
+    pub(crate) fn retry_delay(
+        &self,
+        attempt: u8,
+        method: &Method,
+        might_have_reached_discord: bool,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        if might_have_reached_discord && !self.retry_non_idempotent && !is_idempotent(method) {
+            return None;
+        }
+
+        Some(self.delay(attempt))
+    }
+
+    /// The exponential backoff delay for the given attempt, with a random
+    /// jitter of up to half of the delay
+    pub(crate) fn delay(&self, attempt: u8) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(u32::from(attempt.saturating_sub(1))))
+            .min(self.max_delay);
+
+        let half_millis =
+            u64::try_from(delay.checked_div(2).unwrap_or_default().as_millis()).unwrap_or(u64::MAX);
+        let jitter = random()
+            .checked_rem(half_millis.saturating_add(1))
+            .unwrap_or_default();
+
+        delay.saturating_sub(Duration::from_millis(jitter))
+    }
+}
+
+/// Whether sending a request with the method more than once has the same
+/// effect as sending it once
+fn is_idempotent(method: &Method) -> bool {
+    [
+        Method::GET,
+        Method::HEAD,
+        Method::OPTIONS,
+        Method::PUT,
+        Method::DELETE,
+    ]
+    .contains(method)
+}
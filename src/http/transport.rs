@@ -0,0 +1,56 @@
+use std::fmt::Debug;
+
+use anyhow::Error;
+use futures_util::{future::BoxFuture, FutureExt, TryFutureExt};
+use hyper::{client::connect::Connect, Body, Client, Request as HyperRequest, Response};
+
+/// Sends the HTTP requests of a context
+///
+/// The default is a hyper client with rustls, implement this to use a custom
+/// connector, such as one with a different TLS root store or one that
+/// connects to a proxy over a Unix socket, or to return canned responses in
+/// tests, then set it in `ContextConfig.transport`
+///
+/// It's implemented for every hyper client, so a client with a custom
+/// connector can be used as is
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use anyhow::Error;
+/// use daybreak::{http::transport::Transport, Context, ContextConfig};
+/// use futures_util::{future::BoxFuture, FutureExt};
+/// use hyper::{Body, Request, Response};
+///
+/// #[derive(Debug)]
+/// struct EmptyList;
+///
+/// impl Transport for EmptyList {
+///     fn send(&self, _: Request<Body>) -> BoxFuture<'_, Result<Response<Body>, Error>> {
+///         async { Ok(Response::new(Body::from("[]"))) }.boxed()
+///     }
+/// }
+///
+/// let ctx = Context::new(&ContextConfig {
+///     transport: Some(Arc::new(EmptyList)),
+///     ..ContextConfig::default()
+/// });
+/// ```
+pub trait Transport: Debug + Send + Sync {
+    /// Send the request, returning its response
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the request can't be sent or the response can't
+    /// be received, if it's a `hyper::Error`, whether it's a connection error
+    /// is used to decide whether the request may be retried
+    fn send(&self, request: HyperRequest<Body>) -> BoxFuture<'_, Result<Response<Body>, Error>>;
+}
+
+impl<C: Connect + Clone + Send + Sync + 'static> Transport for Client<C> {
+    fn send(&self, request: HyperRequest<Body>) -> BoxFuture<'_, Result<Response<Body>, Error>> {
+        self.request(request).map_err(Error::new).boxed()
+    }
+}
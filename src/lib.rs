@@ -59,9 +59,24 @@
     dead_code,
 )]
 
-use enumflags2::{BitFlag, BitFlags};
-use http::Http;
-use model::permission::{self, Permissions};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use cache::PermissionCache;
+use enumflags2::BitFlags;
+use http::{
+    invalid_request::{InvalidRequestPolicy, InvalidRequests},
+    middleware::Middleware,
+    rate_limit::RateLimiter,
+    retry::RetryPolicy,
+    transport::Transport,
+};
+use model::{
+    permission::{self, Permissions},
+    Id,
+};
 use thiserror::Error;
 
 /// # Example
@@ -101,31 +116,80 @@ macro_rules! variants_documented {
 /// ```
 macro_rules! http_errors_doc {
     () => {
-        "# Errors\n\nReturns [`crate::UserError::MissingPermissions`] when the bot doesn't have \
-         the required permissions\n\nOr an `anyhow::Error` on an HTTP or deserialization error"
+        "# Errors\n\nReturns [`crate::UserError::MissingPermissions`] or \
+         [`crate::UserError::MissingChannelPermissions`] when the bot doesn't have the required \
+         permissions\n\nReturns [`crate::http::error::RateLimitError`] when the request is still \
+         rate limited after being retried\n\nReturns [`crate::http::error::TimeoutError`] when the \
+         request doesn't complete within its timeout\n\nReturns \
+         [`crate::http::error::InvalidRequestLimitError`] when too many invalid requests were made \
+         recently\n\nReturns [`crate::http::error::ResponseTooLargeError`] when the response is \
+         bigger than `ContextConfig.max_response_size`\n\nReturns \
+         [`crate::http::error::DiscordApiError`] when Discord responds with an error, if it's \
+         something the user can fix, a [`crate::UserError`] is attached to it as context, so you \
+         can downcast to either of them\n\nOr an `anyhow::Error` on an HTTP or deserialization \
+         error"
     };
 }
 
+/// Caching the bot's permissions to check them before sending requests
+mod cache;
+/// Context methods about channels and messages
+mod channel;
 /// Context methods about guild emojis and stickers
 mod emoji;
+/// Context methods about guilds, their members and bans
+mod guild;
 /// Implementation of making requests to Discord
 pub mod http;
 /// Discord objects and (de)serialization implementations on them
 pub mod model;
+/// Logging users in with Discord and making requests on their behalf
+pub mod oauth2;
+/// Context methods about guild scheduled events
+mod scheduled_event;
 /// Tests for everything in Daybreak
 #[cfg(test)]
 mod tests;
+/// Context methods about the current user
+mod user;
+/// A client to execute webhooks without a bot token
+pub mod webhook;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[allow(clippy::multiple_inherent_impl)]
 /// Brings all of the stateful structs together
+///
+/// Cloning this is cheap, and the clones share the same state
 pub struct Context {
-    /// The bot's token, unlike `Config.token`, this has `Bot ` prepended to it
-    pub(crate) token: String,
-    /// The permissions the bot has, this will be removed at release
-    pub(crate) permissions: BitFlags<Permissions>,
-    /// The HTTP client used in the crate
-    pub(crate) http: Http,
+    /// The bot's token, unlike `Config.token`, this has `Bot ` prepended to it,
+    /// `None` if the config's token is empty, such as in a
+    /// [`webhook::WebhookClient`]
+    pub(crate) token: Option<String>,
+    /// The bot's permissions in the guilds and channels they're cached for,
+    /// shared by every clone of the context
+    pub(crate) permissions: Arc<Mutex<PermissionCache>>,
+    /// The transport used to send requests
+    pub(crate) http: Arc<dyn Transport>,
+    /// The URL requests are made to, with the API version appended
+    pub(crate) api_url: String,
+    /// The rate limiter used for every request made with the context, `None`
+    /// if the requests are sent to a proxy, which handles the rate limits
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    /// How many times to retry a request that was rate limited
+    pub(crate) max_rate_limit_retries: u8,
+    /// How to retry requests that failed because of transient errors
+    pub(crate) retry_policy: RetryPolicy,
+    /// The middleware called around every request
+    pub(crate) middleware: Vec<Arc<dyn Middleware>>,
+    /// How long to wait for a request to complete by default
+    pub(crate) timeout: Option<Duration>,
+    /// The invalid requests made recently, shared by every clone of the
+    /// context
+    pub(crate) invalid_requests: Arc<InvalidRequests>,
+    /// What to do when too many invalid requests were made recently
+    pub(crate) invalid_request_policy: InvalidRequestPolicy,
+    /// The maximum size of a response body in bytes
+    pub(crate) max_response_size: Option<usize>,
 }
 
 impl Context {
@@ -142,6 +206,7 @@ impl Context {
     /// static CTX: Lazy<Context> = Lazy::new(|| {
     ///     Context::new(&ContextConfig {
     ///         token: "my totally real token",
+    ///         ..ContextConfig::default()
     ///     })
     /// });
     ///
@@ -150,10 +215,31 @@ impl Context {
     #[must_use]
     #[allow(clippy::new_without_default)]
     pub fn new(config: &ContextConfig<'_>) -> Self {
+        let http = config.transport.clone().unwrap_or_else(|| {
+            if config.proxy_url.is_some() {
+                Arc::new(http::create_proxied())
+            } else {
+                Arc::new(http::create(config.allow_http))
+            }
+        });
+        let api_url = match config.proxy_url {
+            Some(proxy_url) => format!("{proxy_url}/api/v{}", config.api_version),
+            None => format!("{}/v{}", config.api_url, config.api_version),
+        };
+
         Self {
-            token: format!("Bot {}", config.token),
-            permissions: Permissions::all(),
-            http: http::create(),
+            token: (!config.token.is_empty()).then(|| format!("Bot {}", config.token)),
+            permissions: Arc::default(),
+            http,
+            api_url,
+            rate_limiter: config.proxy_url.is_none().then(Arc::default),
+            max_rate_limit_retries: config.max_rate_limit_retries,
+            retry_policy: config.retry_policy,
+            middleware: config.middleware.clone(),
+            timeout: config.timeout,
+            invalid_requests: Arc::default(),
+            invalid_request_policy: config.invalid_request_policy,
+            max_response_size: config.max_response_size,
         }
     }
 }
@@ -163,7 +249,73 @@ impl Context {
 pub struct ContextConfig<'conf> {
     /// The bot's token as obtained from
     /// [Discord applications page](https://discord.com/developers/applications)
+    ///
+    /// The `Authorization` header isn't sent if this is empty
     pub token: &'conf str,
+    /// The base URL of the API, without the version or a trailing slash
+    ///
+    /// Change this to send requests to a mock server for example
+    pub api_url: &'conf str,
+    /// The version of the API to use
+    pub api_version: u8,
+    /// Whether to allow plain HTTP connections, they're HTTPS only otherwise
+    pub allow_http: bool,
+    /// How many times to retry a request that was rate limited before
+    /// returning an [`http::error::RateLimitError`]
+    pub max_rate_limit_retries: u8,
+    /// How to retry requests that failed because of transient errors, such as
+    /// a `502 Bad Gateway` or a dropped connection
+    pub retry_policy: RetryPolicy,
+    /// The middleware to call around every request, in order
+    pub middleware: Vec<Arc<dyn Middleware>>,
+    /// The transport to send requests with, a hyper client with rustls is
+    /// used if this is `None`, in which case `allow_http` is respected
+    pub transport: Option<Arc<dyn Transport>>,
+    /// The URL of a rate limiting proxy to send requests to instead of
+    /// Discord, such as `http://localhost:8080`, without a trailing slash
+    ///
+    /// Use this to share rate limits between processes of the same bot, the
+    /// requests are sent over plain HTTP/2 and the context doesn't rate
+    /// limit them itself, `api_url` is ignored then, refer to
+    /// `http::proxy::Proxy` for the proxy that's built into Daybreak
+    pub proxy_url: Option<&'conf str>,
+    /// How long to wait for a request to complete before returning an
+    /// [`http::error::TimeoutError`], `None` to wait forever
+    ///
+    /// This includes the time spent waiting for the rate limiter and retrying
+    /// the request, use [`http::Request::with_timeout`] to override it for a
+    /// request
+    ///
+    /// A timed out request is cancelled the same way as when its future is
+    /// dropped, which lets the next request to the same route through
+    pub timeout: Option<Duration>,
+    /// What to do when too many invalid requests were made recently, to
+    /// avoid Cloudflare banning the IP
+    pub invalid_request_policy: InvalidRequestPolicy,
+    /// The maximum size of a response body in bytes, larger responses return
+    /// an [`http::error::ResponseTooLargeError`], `None` to allow any size
+    ///
+    /// The default is 32 MiB
+    pub max_response_size: Option<usize>,
+}
+
+impl Default for ContextConfig<'_> {
+    fn default() -> Self {
+        Self {
+            token: "",
+            api_url: "https://discord.com/api",
+            api_version: 10,
+            allow_http: false,
+            max_rate_limit_retries: 3,
+            retry_policy: RetryPolicy::default(),
+            middleware: vec![],
+            transport: None,
+            proxy_url: None,
+            timeout: Some(Duration::from_secs(10)),
+            invalid_request_policy: InvalidRequestPolicy::default(),
+            max_response_size: Some(32 * 1024 * 1024),
+        }
+    }
 }
 
 /// A user-facing error
@@ -208,6 +360,34 @@ pub enum UserError {
         permission::to_pretty_string(*.0),
     )]
     MissingPermissions(BitFlags<Permissions>),
+    /// The bot is missing some permissions in the channel
+    #[error(
+        "Please give the bot these permissions in <#{1}>:\n{}",
+        permission::to_pretty_string(*.0),
+    )]
+    MissingChannelPermissions(BitFlags<Permissions>, Id),
+    /// The bot can't see the channel or guild
+    #[error("Please make sure the bot can see this channel")]
+    MissingAccess,
+    /// The user doesn't allow the bot to send them messages
+    #[error(
+        "I can't send you direct messages, please allow direct messages from server members in \
+         your privacy settings"
+    )]
+    CannotMessageUser,
+    /// The bot's highest role has to be above the role with the given name to
+    /// do this
+    #[error("The bot's role must be above @{}", .0.trim_start_matches('@'))]
+    RoleTooHigh(String),
+    /// The role isn't in the guild, it might have been deleted
+    #[error("I can't find that role, maybe it was deleted")]
+    UnknownRole,
+    /// The member is the owner of the guild, so the bot can't do this to them
+    #[error("I can't do this to the owner of the server")]
+    MemberIsOwner,
+    /// The audit log reason of a request is too long
+    #[error("Please keep the reason at most 512 characters long")]
+    AuditLogReasonTooLong,
     /// The error is user-defined
     #[error("{0}")]
     Custom(anyhow::Error),
@@ -248,4 +428,16 @@ impl UserError {
     pub fn custom(err: impl Into<anyhow::Error>) -> Self {
         Self::Custom(err.into())
     }
+
+    /// The error for the missing permissions, naming the channel if it's
+    /// given
+    pub(crate) const fn missing_permissions(
+        permissions: BitFlags<Permissions>,
+        channel_id: Option<Id>,
+    ) -> Self {
+        match channel_id {
+            Some(channel_id) => Self::MissingChannelPermissions(permissions, channel_id),
+            None => Self::MissingPermissions(permissions),
+        }
+    }
 }
@@ -36,6 +36,8 @@ pub mod invite;
 pub mod member;
 #[doc = discord_url!("https://discord.com/developers/docs/resources/channel#message-object")]
 pub mod message;
+#[doc = discord_url!("https://discord.com/developers/docs/topics/oauth2")]
+pub mod oauth2;
 #[doc =discord_url!("https://discord.com/developers/docs/topics/permissions")]
 pub mod permission;
 #[doc = discord_url!("https://discord.com/developers/docs/topics/gateway#presence")]
@@ -48,15 +50,17 @@ pub mod scheduled_event;
 pub mod user;
 #[doc = discord_url!("https://discord.com/developers/docs/resources/voice#voice-resource")]
 pub mod voice;
+#[doc = discord_url!("https://discord.com/developers/docs/resources/webhook")]
+pub mod webhook;
 
 use std::{fmt::Display, num::ParseIntError};
 
 use anyhow::Error;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use time::{Duration, OffsetDateTime};
 
 #[doc = discord_url!("https://discord.com/developers/docs/reference#snowflakes")]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
 #[serde(try_from = "String")]
 pub struct Id(pub u64);
 
@@ -66,6 +70,12 @@ impl Display for Id {
     }
 }
 
+impl Serialize for Id {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 impl TryFrom<String> for Id {
     type Error = ParseIntError;
 
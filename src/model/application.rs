@@ -2,7 +2,7 @@ use enumflags2::bitflags;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use super::{permission::Permissions, user::User, Id};
+use super::{permission::PermissionSet, user::User, Id};
 
 #[doc = discord_url!(
     "https://discord.com/developers/docs/resources/application\
@@ -56,7 +56,7 @@ pub enum ApplicationFlags {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct InstallParams {
     pub scopes: Vec<String>,
-    pub permissions: Permissions,
+    pub permissions: PermissionSet,
 }
 
 #[doc = discord_url!("https://discord.com/developers/docs/topics/teams#data-models-team-object")]
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use time::OffsetDateTime;
 
-use super::{member::ThreadMember, permission::Permissions, user::User, Id};
+use super::{member::ThreadMember, permission::PermissionSet, user::User, Id};
 
 #[doc = discord_url!(
     "https://discord.com/developers/docs/resources/channel#channel-object-channel-structure"
@@ -35,7 +35,7 @@ pub struct Channel {
     pub thread_metadata: Option<Thread>,
     pub member: Option<ThreadMember>,
     pub default_auto_archive_duration: Option<u16>,
-    pub permissions: Option<Permissions>,
+    pub permissions: Option<PermissionSet>,
     pub flags: Option<BitFlags<ChannelFlags>>,
     pub total_message_sent: Option<u32>,
     #[doc = discord_url!("https://discord.com/developers/docs/topics/gateway#thread-create")]
@@ -126,8 +126,8 @@ pub struct ThreadSync {
 pub struct PermissionOverwrite {
     pub id: Id,
     pub kind: PermissionOverwriteKind,
-    pub allow: BitFlags<Permissions>,
-    pub deny: BitFlags<Permissions>,
+    pub allow: PermissionSet,
+    pub deny: PermissionSet,
 }
 
 #[doc = discord_url!(
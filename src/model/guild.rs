@@ -1,3 +1,5 @@
+use std::cmp::Reverse;
+
 use enumflags2::{bitflags, BitFlags};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
@@ -7,7 +9,7 @@ use super::{
     channel::Channel,
     emoji::{Emoji, Sticker},
     member::Member,
-    permission::Permissions,
+    permission::{self, PermissionSet, Permissions},
     presence::Activity,
     role::Role,
     scheduled_event::ScheduledEvent,
@@ -15,6 +17,7 @@ use super::{
     voice::VoiceState,
     Id,
 };
+use crate::UserError;
 
 #[doc = discord_url!(
     "https://discord.com/developers/docs/resources/guild#guild-object-guild-structure"
@@ -29,7 +32,7 @@ pub struct Guild {
     pub discovery_splash: Option<String>,
     pub owner: Option<bool>,
     pub owner_id: Id,
-    pub permissions: Option<Permissions>,
+    pub permissions: Option<PermissionSet>,
     pub region: Option<String>,
     pub afk_channel_id: Option<Id>,
     pub afk_timeout: u16,
@@ -108,6 +111,148 @@ pub struct Guild {
     pub guild_scheduled_events: Option<Vec<ScheduledEvent>>,
 }
 
+impl Guild {
+    /// Whether the member is the owner of the guild, `member.user` has to be
+    /// set to know this
+    #[must_use]
+    pub fn is_owner(&self, member: &Member) -> bool {
+        member
+            .user
+            .as_ref()
+            .map_or(false, |user| user.id == self.owner_id)
+    }
+
+    /// The highest of the member's roles, or the `@everyone` role if the
+    /// member has no roles, `None` if the role isn't in `self.roles`
+    ///
+    /// Roles with the same position are ordered by their IDs, the older role
+    /// being higher, like Discord does
+    #[must_use]
+    pub fn highest_role(&self, member: &Member) -> Option<&Role> {
+        self.roles
+            .iter()
+            .filter(|role| role.id == self.id || member.roles.contains(&role.id))
+            .max_by_key(|role| (role.position, Reverse(role.id)))
+    }
+
+    /// Check that the bot can kick, ban, time out or edit the member, which
+    /// requires the bot's highest role to be above the member's, unless the
+    /// bot is the owner of the guild
+    ///
+    /// `bot` is the bot's member in the guild, `member.user` has to be set for
+    /// both of them, this doesn't check the bot's permissions
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UserError::MemberIsOwner`] if the member is the owner of the
+    /// guild, or [`UserError::RoleTooHigh`] if the member's highest role isn't
+    /// below the bot's
+    pub fn check_member_hierarchy(&self, bot: &Member, member: &Member) -> Result<(), UserError> {
+        if self.is_owner(member) {
+            return Err(UserError::MemberIsOwner);
+        }
+
+        match self.highest_role(member) {
+            Some(role) => self.check_role_hierarchy(bot, role.id),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that the bot can edit, delete, assign or remove the role with
+    /// the given ID, which requires the bot's highest role to be above it,
+    /// unless the bot is the owner of the guild
+    ///
+    /// `bot` is the bot's member in the guild, with its `user` set, this
+    /// doesn't check the bot's permissions
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UserError::UnknownRole`] if the guild doesn't have the role,
+    /// or [`UserError::RoleTooHigh`] if the role isn't below the bot's
+    /// highest role
+    pub fn check_role_hierarchy(&self, bot: &Member, role_id: Id) -> Result<(), UserError> {
+        let Some(role) = self.roles.iter().find(|role| role.id == role_id) else {
+            return Err(UserError::UnknownRole);
+        };
+        if self.is_owner(bot) {
+            return Ok(());
+        }
+
+        let is_below_bot = self.highest_role(bot).map_or(false, |bot_role| {
+            (bot_role.position, Reverse(bot_role.id)) > (role.position, Reverse(role.id))
+        });
+
+        if is_below_bot {
+            Ok(())
+        } else {
+            Err(UserError::RoleTooHigh(role.name.clone()))
+        }
+    }
+
+    /// The permissions of the member in the guild, not counting the
+    /// overwrites of any channel
+    ///
+    /// These are the permissions of the `@everyone` role and the member's
+    /// roles combined, the owner of the guild and members with the
+    /// `Administrator` permission have every permission, `member.user` has
+    /// to be set to know if the member is the owner
+    ///
+    /// Members that are timed out or haven't passed membership screening yet
+    /// only keep `ViewChannel` and `ReadMessageHistory`
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/topics/permissions#permission-overwrites"
+    )]
+    #[must_use]
+    pub fn member_permissions(&self, member: &Member) -> BitFlags<Permissions> {
+        permission::restrict(self.base_permissions(member), member)
+    }
+
+    /// The permissions of the member in the guild, without restricting them
+    /// if the member is timed out or pending
+    pub(crate) fn base_permissions(&self, member: &Member) -> BitFlags<Permissions> {
+        if self.is_owner(member) {
+            return BitFlags::all();
+        }
+
+        let permissions = self
+            .roles
+            .iter()
+            .filter(|role| role.id == self.id || member.roles.contains(&role.id))
+            .fold(BitFlags::empty(), |permissions, role| {
+                permissions | role.permissions.flags()
+            });
+
+        if permissions.contains(Permissions::Administrator) {
+            BitFlags::all()
+        } else {
+            permissions
+        }
+    }
+
+    /// The permissions of the member in the channel of the guild, applying
+    /// the channel's overwrites to [`Self::member_permissions`], including the
+    /// permissions implicitly denied, such as every permission if the member
+    /// can't view the channel, and restricting them if the member is timed out
+    /// or pending
+    ///
+    /// Threads don't have overwrites, so pass their parent channel instead
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/topics/permissions#permission-overwrites"
+    )]
+    #[must_use]
+    pub fn channel_permissions(&self, channel: &Channel, member: &Member) -> BitFlags<Permissions> {
+        permission::restrict(
+            permission::apply_overwrites(
+                self.base_permissions(member),
+                channel.permission_overwrites.as_deref().unwrap_or_default(),
+                self.id,
+                member,
+            ),
+            member,
+        )
+    }
+}
+
 #[doc = discord_url!(
     "https://discord.com/developers/docs/resources/guild#guild-object-verification-level"
 )]
@@ -243,6 +388,15 @@ pub struct UnavailableGuild {
     pub unavailable: bool,
 }
 
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/resources/guild#ban-object-ban-structure"
+)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Ban {
+    pub reason: Option<String>,
+    pub user: User,
+}
+
 #[doc = discord_url!(
     "https://discord.com/developers/docs/topics/gateway#guild-ban-add-guild-ban-add-event-fields"
 )]
@@ -255,3 +409,17 @@ pub struct GuildBan {
     pub guild_id: Id,
     pub user: User,
 }
+
+/// A partial guild returned when getting the guilds of the current user
+#[doc = discord_url!("https://discord.com/developers/docs/resources/user#get-current-user-guilds")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CurrentUserGuild {
+    pub id: Id,
+    pub name: String,
+    pub icon: Option<String>,
+    pub owner: bool,
+    pub permissions: PermissionSet,
+    pub features: Vec<String>,
+    pub approximate_member_count: Option<u32>,
+    pub approximate_presence_count: Option<u32>,
+}
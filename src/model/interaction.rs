@@ -8,7 +8,7 @@ use super::{
     emoji::Emoji,
     member::Member,
     message::{Attachment, Message},
-    permission::Permissions,
+    permission::PermissionSet,
     role::Role,
     user::User,
     Id,
@@ -31,7 +31,7 @@ pub struct Interaction {
     pub token: String,
     pub version: u8,
     pub message: Option<Message>,
-    pub app_permissions: Option<Permissions>,
+    pub app_permissions: Option<PermissionSet>,
     pub locale: Option<String>,
     pub guild_locale: Option<String>,
 }
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use super::{
-    permission::Permissions,
+    permission::PermissionSet,
     presence::{Activity, Presence},
     user::User,
     Id,
@@ -23,7 +23,7 @@ pub struct Member {
     pub deaf: Option<bool>,
     pub mute: Option<bool>,
     pub pending: Option<bool>,
-    pub permissions: Option<Permissions>,
+    pub permissions: Option<PermissionSet>,
     pub communication_disabled_until: Option<OffsetDateTime>,
     #[doc = discord_url!(
         "https://discord.com/developers/docs/topics/gateway\
@@ -36,6 +36,16 @@ pub struct Member {
     pub guild_id: Option<Id>,
 }
 
+impl Member {
+    /// Whether the member is timed out, so they can only view channels and
+    /// read their message history until `communication_disabled_until`
+    #[must_use]
+    pub fn is_timed_out(&self) -> bool {
+        self.communication_disabled_until
+            .map_or(false, |until| until > OffsetDateTime::now_utc())
+    }
+}
+
 #[doc = discord_url!(
     "https://discord.com/developers/docs/resources/channel\
     #thread-member-object-thread-member-structure"
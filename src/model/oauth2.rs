@@ -0,0 +1,102 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/topics/oauth2#shared-resources-oauth2-scopes"
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    ActivitiesRead,
+    ActivitiesWrite,
+    ApplicationsBuildsRead,
+    ApplicationsBuildsUpload,
+    ApplicationsCommands,
+    ApplicationsCommandsUpdate,
+    ApplicationsCommandsPermissionsUpdate,
+    ApplicationsEntitlements,
+    ApplicationsStoreUpdate,
+    Bot,
+    Connections,
+    DmChannelsRead,
+    Email,
+    GdmJoin,
+    Guilds,
+    GuildsJoin,
+    GuildsMembersRead,
+    Identify,
+    MessagesRead,
+    RelationshipsRead,
+    RoleConnectionsWrite,
+    Rpc,
+    RpcActivitiesWrite,
+    RpcNotificationsRead,
+    RpcVoiceRead,
+    RpcVoiceWrite,
+    Voice,
+    WebhookIncoming,
+}
+
+impl Scope {
+    /// The name of the scope as Discord expects it
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ActivitiesRead => "activities.read",
+            Self::ActivitiesWrite => "activities.write",
+            Self::ApplicationsBuildsRead => "applications.builds.read",
+            Self::ApplicationsBuildsUpload => "applications.builds.upload",
+            Self::ApplicationsCommands => "applications.commands",
+            Self::ApplicationsCommandsUpdate => "applications.commands.update",
+            Self::ApplicationsCommandsPermissionsUpdate => {
+                "applications.commands.permissions.update"
+            }
+            Self::ApplicationsEntitlements => "applications.entitlements",
+            Self::ApplicationsStoreUpdate => "applications.store.update",
+            Self::Bot => "bot",
+            Self::Connections => "connections",
+            Self::DmChannelsRead => "dm_channels.read",
+            Self::Email => "email",
+            Self::GdmJoin => "gdm.join",
+            Self::Guilds => "guilds",
+            Self::GuildsJoin => "guilds.join",
+            Self::GuildsMembersRead => "guilds.members.read",
+            Self::Identify => "identify",
+            Self::MessagesRead => "messages.read",
+            Self::RelationshipsRead => "relationships.read",
+            Self::RoleConnectionsWrite => "role_connections.write",
+            Self::Rpc => "rpc",
+            Self::RpcActivitiesWrite => "rpc.activities.write",
+            Self::RpcNotificationsRead => "rpc.notifications.read",
+            Self::RpcVoiceRead => "rpc.voice.read",
+            Self::RpcVoiceWrite => "rpc.voice.write",
+            Self::Voice => "voice",
+            Self::WebhookIncoming => "webhook.incoming",
+        }
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Scope {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/topics/oauth2\
+    #authorization-code-grant-access-token-response"
+)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AccessToken {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    pub refresh_token: Option<String>,
+    pub scope: String,
+}
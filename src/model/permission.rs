@@ -1,8 +1,15 @@
-use std::fmt::Display;
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
 
 use enumflags2::{bitflags, BitFlags};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
+use super::{
+    channel::{PermissionOverwrite, PermissionOverwriteKind},
+    member::Member,
+    Id,
+};
+
 #[bitflags]
 #[repr(u64)]
 #[doc =discord_url!(
@@ -102,6 +109,101 @@ impl Display for Permissions {
     }
 }
 
+/// A set of permissions, (de)serialized as the decimal string Discord uses,
+/// such as `"1071698660929"`
+///
+/// The bits that aren't known [`Permissions`] yet are kept, so that they're
+/// serialized back as they were received
+#[doc = discord_url!("https://discord.com/developers/docs/topics/permissions#permissions")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct PermissionSet {
+    /// The known permissions in the set
+    flags: BitFlags<Permissions>,
+    /// The bits of the set that aren't known permissions
+    unknown_bits: u64,
+}
+
+impl PermissionSet {
+    /// Create a set from its bits, keeping the bits that aren't known
+    /// permissions
+    #[must_use]
+    pub fn from_bits(bits: u64) -> Self {
+        let flags = BitFlags::from_bits_truncate(bits);
+        Self {
+            flags,
+            unknown_bits: bits & !flags.bits(),
+        }
+    }
+
+    /// The bits of the set, including the ones that aren't known permissions
+    #[must_use]
+    pub const fn bits(self) -> u64 {
+        self.flags.bits_c() | self.unknown_bits
+    }
+
+    /// The known permissions in the set
+    #[must_use]
+    pub const fn flags(self) -> BitFlags<Permissions> {
+        self.flags
+    }
+
+    /// Whether the set has all the given permissions
+    #[must_use]
+    pub fn contains(self, permissions: impl Into<BitFlags<Permissions>>) -> bool {
+        self.flags.contains(permissions)
+    }
+}
+
+impl From<BitFlags<Permissions>> for PermissionSet {
+    fn from(flags: BitFlags<Permissions>) -> Self {
+        Self {
+            flags,
+            unknown_bits: 0,
+        }
+    }
+}
+
+impl From<Permissions> for PermissionSet {
+    fn from(permission: Permissions) -> Self {
+        BitFlags::from(permission).into()
+    }
+}
+
+impl From<PermissionSet> for BitFlags<Permissions> {
+    fn from(set: PermissionSet) -> Self {
+        set.flags
+    }
+}
+
+impl Display for PermissionSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+
+impl FromStr for PermissionSet {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_bits(s.parse()?))
+    }
+}
+
+impl Serialize for PermissionSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl TryFrom<String> for PermissionSet {
+    type Error = ParseIntError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 #[must_use]
 pub fn to_pretty_string(permissions: BitFlags<Permissions>) -> String {
     permissions
@@ -110,3 +212,93 @@ pub fn to_pretty_string(permissions: BitFlags<Permissions>) -> String {
         .collect::<Vec<String>>()
         .join("\n")
 }
+
+/// Apply the overwrites of a channel to the permissions of a member in its
+/// guild
+///
+/// The overwrite of `@everyone` is applied first, then the overwrites of the
+/// member's roles together, then the overwrite of the member, after which
+/// the permissions implicitly denied by the missing ones are removed
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/topics/permissions#permission-overwrites"
+)]
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/topics/permissions#implicit-permissions"
+)]
+pub(crate) fn apply_overwrites(
+    permissions: BitFlags<Permissions>,
+    overwrites: &[PermissionOverwrite],
+    guild_id: Id,
+    member: &Member,
+) -> BitFlags<Permissions> {
+    if permissions.contains(Permissions::Administrator) {
+        return BitFlags::all();
+    }
+
+    let user_id = member.user.as_ref().map(|user| user.id);
+
+    let permissions = apply_overwrite(permissions, overwrites, |overwrite| {
+        overwrite.kind == PermissionOverwriteKind::Role && overwrite.id == guild_id
+    });
+    let permissions = apply_overwrite(permissions, overwrites, |overwrite| {
+        overwrite.kind == PermissionOverwriteKind::Role
+            && overwrite.id != guild_id
+            && member.roles.contains(&overwrite.id)
+    });
+    let permissions = apply_overwrite(permissions, overwrites, |overwrite| {
+        overwrite.kind == PermissionOverwriteKind::Member && Some(overwrite.id) == user_id
+    });
+
+    if !permissions.contains(Permissions::ViewChannel) {
+        BitFlags::empty()
+    } else if !permissions.contains(Permissions::SendMessages) {
+        permissions
+            & !(Permissions::MentionEveryone
+                | Permissions::SendTtsMessages
+                | Permissions::AttachFiles
+                | Permissions::EmbedLinks)
+    } else {
+        permissions
+    }
+}
+
+/// Remove every permission other than `ViewChannel` and
+/// `ReadMessageHistory` if the member is timed out or hasn't passed
+/// membership screening yet, unless they're an administrator
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/resources/guild#modify-guild-member-json-params"
+)]
+pub(crate) fn restrict(
+    permissions: BitFlags<Permissions>,
+    member: &Member,
+) -> BitFlags<Permissions> {
+    if permissions.contains(Permissions::Administrator)
+        || !(member.is_timed_out() || member.pending == Some(true))
+    {
+        permissions
+    } else {
+        permissions & (Permissions::ViewChannel | Permissions::ReadMessageHistory)
+    }
+}
+
+/// Deny and then allow the permissions of the overwrites that apply combined
+fn apply_overwrite(
+    permissions: BitFlags<Permissions>,
+    overwrites: &[PermissionOverwrite],
+    applies: impl Fn(&PermissionOverwrite) -> bool,
+) -> BitFlags<Permissions> {
+    let (allow, deny) = overwrites
+        .iter()
+        .filter(|overwrite| applies(overwrite))
+        .fold(
+            (BitFlags::empty(), BitFlags::empty()),
+            |(allow, deny), overwrite| {
+                (
+                    allow | overwrite.allow.flags(),
+                    deny | overwrite.deny.flags(),
+                )
+            },
+        );
+
+    (permissions & !deny) | allow
+}
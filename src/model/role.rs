@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::{permission::Permissions, Id};
+use super::{permission::PermissionSet, Id};
 
 #[doc = discord_url!(
     "https://discord.com/developers/docs/topics/permissions#role-object-role-structure"
@@ -14,7 +14,7 @@ pub struct Role {
     pub icon: Option<String>,
     pub unicode_emoji: Option<String>,
     pub position: u8,
-    pub permissions: Permissions,
+    pub permissions: PermissionSet,
     pub managed: bool,
     pub mentionable: bool,
     pub tags: Option<RoleTags>,
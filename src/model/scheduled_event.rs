@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use time::OffsetDateTime;
 
-use super::{user::User, Id};
+use super::{member::Member, user::User, Id};
 
 #[doc =discord_url!(
     "https://discord.com/developers/docs/resources/guild-scheduled-event\
@@ -72,6 +72,17 @@ pub enum ScheduledEventEntityType {
     External = 3,
 }
 
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/resources/guild-scheduled-event\
+    #guild-scheduled-event-user-object-guild-scheduled-event-user-structure"
+)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledEventSubscriber {
+    pub guild_scheduled_event_id: Id,
+    pub user: User,
+    pub member: Option<Member>,
+}
+
 #[doc = discord_url!(
     "https://discord.com/developers/docs/topics/gateway\
     #guild-scheduled-event-user-add-guild-scheduled-event-user-add-event-fields"
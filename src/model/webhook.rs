@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{embed::Embed, interaction::Component, message::AllowedMentions};
+
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/resources/webhook#execute-webhook-jsonform-params"
+)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ExecuteWebhook {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<Component>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_name: Option<String>,
+}
+
+#[doc = discord_url!(
+    "https://discord.com/developers/docs/resources/webhook#edit-webhook-message-jsonform-params"
+)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EditWebhookMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<Component>>,
+}
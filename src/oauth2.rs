@@ -0,0 +1,283 @@
+use anyhow::Error;
+use enumflags2::{BitFlag, BitFlags};
+use hyper::Method;
+use serde::Serialize;
+
+use crate::{
+    http::Request,
+    model::{
+        oauth2::{AccessToken, Scope},
+        permission::Permissions,
+        Id,
+    },
+    Context, ContextConfig,
+};
+
+/// The page users are sent to to authorize the application, it's opened in
+/// their browsers so it doesn't depend on the API URL or the proxy
+const AUTHORIZE_URL: &str = "https://discord.com/oauth2/authorize";
+
+/// The options of an authorization URL
+#[doc = discord_url!("https://discord.com/developers/docs/topics/oauth2#authorization-code-grant")]
+#[doc = discord_url!("https://discord.com/developers/docs/topics/oauth2#bot-authorization-flow")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Authorization<'auth> {
+    /// The scopes to request
+    pub scopes: &'auth [Scope],
+    /// The URL to redirect to with the code, it must be one of the redirect
+    /// URLs of the application, it's required unless the only scope is `bot`
+    pub redirect_uri: Option<&'auth str>,
+    /// A unique string to check that the redirect is for the same user who
+    /// started the flow
+    pub state: Option<&'auth str>,
+    /// The permissions to request for the bot, if the scopes include `bot`
+    pub permissions: Option<BitFlags<Permissions>>,
+    /// The guild to preselect for the bot, if the scopes include `bot`
+    pub guild_id: Option<Id>,
+}
+
+/// The query string of an authorization URL
+#[derive(Serialize)]
+struct AuthorizationQuery<'auth> {
+    /// `code` if there's a redirect URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_type: Option<&'auth str>,
+    /// The ID of the application
+    client_id: Id,
+    /// The space-separated scopes
+    scope: String,
+    /// The URL to redirect to with the code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redirect_uri: Option<&'auth str>,
+    /// The state to check in the redirect
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state: Option<&'auth str>,
+    /// The bits of the permissions to request for the bot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<u64>,
+    /// The guild to preselect for the bot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    guild_id: Option<Id>,
+}
+
+/// The form sent to the token endpoint
+#[derive(Serialize)]
+struct TokenParams<'params> {
+    /// The grant type, such as `authorization_code`
+    grant_type: &'params str,
+    /// The code to exchange
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'params str>,
+    /// The redirect URL the code was sent to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redirect_uri: Option<&'params str>,
+    /// The refresh token to exchange
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<&'params str>,
+    /// The space-separated scopes to request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+}
+
+/// A client to log users in with Discord and get access tokens for them
+///
+/// Use [`Context::new_bearer`] to make requests with an access token
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use daybreak::{
+///     model::{oauth2::Scope, Id},
+///     oauth2::{Authorization, OAuth2Client},
+///     Context, ContextConfig,
+/// };
+///
+/// # async fn run(code: &str) -> Result<(), anyhow::Error> {
+/// let oauth2 = OAuth2Client::new(
+///     Id(1234),
+///     "my totally real secret",
+///     &ContextConfig::default(),
+/// );
+///
+/// let url = oauth2.authorization_url(&Authorization {
+///     scopes: &[Scope::Identify, Scope::Guilds],
+///     redirect_uri: Some("https://example.com/callback"),
+///     state: Some("a random string"),
+///     ..Authorization::default()
+/// })?;
+///
+/// // redirect the user to the URL, then get the code from the callback
+/// let token = oauth2
+///     .exchange_code(code, "https://example.com/callback")
+///     .await?;
+/// let ctx = Context::new_bearer(&token.access_token, &ContextConfig::default());
+/// let user = ctx.current_user().await?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct OAuth2Client {
+    /// The context the requests are made with, which authenticates with the
+    /// application's ID and secret
+    pub(crate) ctx: Context,
+    /// The ID of the application
+    pub(crate) client_id: Id,
+}
+
+impl OAuth2Client {
+    /// Create a client for the application with the given ID and secret
+    ///
+    /// The requests are made with a context created from the config, but
+    /// `config.token` is ignored, the ID and secret are sent in the
+    /// `Authorization` header with the `Basic` scheme instead
+    #[must_use]
+    pub fn new(client_id: Id, client_secret: &str, config: &ContextConfig<'_>) -> Self {
+        Self {
+            ctx: Context {
+                token: Some(format!(
+                    "Basic {}",
+                    encode_base64(format!("{client_id}:{client_secret}").as_bytes())
+                )),
+                ..Context::new(config)
+            },
+            client_id,
+        }
+    }
+
+    /// The URL to send the user to to authorize the application, or to add
+    /// the bot to a guild
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query string can't be encoded
+    pub fn authorization_url(&self, authorization: &Authorization<'_>) -> Result<String, Error> {
+        let query = serde_urlencoded::to_string(AuthorizationQuery {
+            response_type: authorization.redirect_uri.map(|_| "code"),
+            client_id: self.client_id,
+            scope: join_scopes(authorization.scopes),
+            redirect_uri: authorization.redirect_uri,
+            state: authorization.state,
+            permissions: authorization.permissions.map(BitFlags::bits),
+            guild_id: authorization.guild_id,
+        })?;
+
+        Ok(format!("{AUTHORIZE_URL}?{query}"))
+    }
+
+    /// Exchange the code the user was redirected with for an access token
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/topics/oauth2#authorization-code-grant"
+    )]
+    #[doc = http_errors_doc!()]
+    pub async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+    ) -> Result<AccessToken, Error> {
+        self.token(TokenParams {
+            code: Some(code),
+            redirect_uri: Some(redirect_uri),
+            ..Self::token_params("authorization_code")
+        })
+        .await
+    }
+
+    /// Exchange the refresh token for a new access token
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/topics/oauth2\
+        #authorization-code-grant-refresh-token-exchange-example"
+    )]
+    #[doc = http_errors_doc!()]
+    pub async fn refresh(&self, refresh_token: &str) -> Result<AccessToken, Error> {
+        self.token(TokenParams {
+            refresh_token: Some(refresh_token),
+            ..Self::token_params("refresh_token")
+        })
+        .await
+    }
+
+    /// Get an access token for the owner of the application, without a user
+    /// authorizing it
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/topics/oauth2#client-credentials-grant"
+    )]
+    #[doc = http_errors_doc!()]
+    pub async fn client_credentials(&self, scopes: &[Scope]) -> Result<AccessToken, Error> {
+        self.token(TokenParams {
+            scope: Some(join_scopes(scopes)),
+            ..Self::token_params("client_credentials")
+        })
+        .await
+    }
+
+    /// The token params with only the grant type
+    const fn token_params(grant_type: &str) -> TokenParams<'_> {
+        TokenParams {
+            grant_type,
+            code: None,
+            redirect_uri: None,
+            refresh_token: None,
+            scope: None,
+        }
+    }
+
+    /// Send the params to the token endpoint
+    async fn token(&self, params: TokenParams<'_>) -> Result<AccessToken, Error> {
+        self.ctx
+            .request_with_params(
+                Request::new(
+                    Permissions::empty(),
+                    Method::POST,
+                    "/oauth2/token".to_owned(),
+                )
+                .with_form(),
+                params,
+            )
+            .await
+    }
+}
+
+impl Context {
+    /// Create a context that authenticates with the given OAuth2 access
+    /// token instead of a bot token, to make requests on behalf of a user
+    ///
+    /// `config.token` is ignored, and only the endpoints allowed by the
+    /// token's scopes can be used
+    #[doc = discord_url!("https://discord.com/developers/docs/topics/oauth2")]
+    #[must_use]
+    pub fn new_bearer(access_token: &str, config: &ContextConfig<'_>) -> Self {
+        Self {
+            token: Some(format!("Bearer {access_token}")),
+            ..Self::new(config)
+        }
+    }
+}
+
+/// Join the scopes with spaces, as Discord expects them
+fn join_scopes(scopes: &[Scope]) -> String {
+    scopes
+        .iter()
+        .map(|scope| scope.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Encode the bytes with the standard base64 alphabet and padding, as the
+/// `Basic` scheme expects them
+fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = chunk.iter().enumerate().fold(0_u32, |triple, (i, &byte)| {
+            triple | (u32::from(byte) << (16 - i * 8))
+        });
+        for i in 0..4 {
+            encoded.push(if i <= chunk.len() {
+                char::from(ALPHABET[((triple >> (18 - i * 6)) & 0x3f) as usize])
+            } else {
+                '='
+            });
+        }
+    }
+    encoded
+}
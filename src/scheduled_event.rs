@@ -0,0 +1,48 @@
+use anyhow::Error;
+use enumflags2::BitFlag;
+use futures_util::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use hyper::Method;
+
+use super::Context;
+use crate::{
+    http::{pagination::Direction, Request},
+    model::{permission::Permissions, scheduled_event::ScheduledEventSubscriber, Id},
+};
+
+impl Context {
+    /// Walk the users subscribed to the scheduled event, with their member
+    /// objects
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/resources/guild-scheduled-event\
+        #get-guild-scheduled-event-users"
+    )]
+    #[doc = http_errors_doc!()]
+    #[must_use]
+    pub fn scheduled_event_subscribers(
+        &self,
+        guild_id: Id,
+        scheduled_event_id: Id,
+    ) -> BoxStream<'_, Result<ScheduledEventSubscriber, Error>> {
+        let request = match Request::new(
+            Permissions::empty(),
+            Method::GET,
+            format!("/guilds/{guild_id}/scheduled-events/{scheduled_event_id}/users"),
+        )
+        .with_guild(guild_id)
+        .with_query(&[("with_member", true)])
+        {
+            Ok(request) => request,
+            Err(err) => return stream::once(async { Err(err) }).boxed(),
+        };
+
+        self.paginate(
+            request,
+            Direction::After,
+            100,
+            |subscriber: &ScheduledEventSubscriber| Some(subscriber.user.id),
+        )
+    }
+}
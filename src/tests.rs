@@ -1,10 +1,24 @@
 #![allow(clippy::unwrap_used)]
 
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Error};
 use enumflags2::BitFlag;
+use futures_util::{
+    future::{self, BoxFuture},
+    FutureExt,
+};
+use hyper::{Body, Request, Response, StatusCode};
 use once_cell::sync::Lazy;
 use time::{Date, Month, PrimitiveDateTime, Time};
+use tokio::time::sleep;
 
 use crate::{
+    http::{cassette::Cassette, create, transport::Transport},
     model::{
         channel::{
             Channel, ChannelFlags, ChannelType, PermissionOverwrite, PermissionOverwriteKind,
@@ -23,12 +37,165 @@ use crate::{
     Context, ContextConfig,
 };
 
+/// The cassette the requests in tests are recorded to and replayed from
+const CASSETTE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/cassette.json");
+
+/// The context used in the tests that send requests to Discord, they're
+/// ignored by default, run them with `--ignored`
+///
+/// Set `DAYBREAK_API_URL` to run the tests against a local stand-in of the
+/// API, plain HTTP is allowed then
+///
+/// Otherwise, the requests are sent to Discord if `DAYBREAK_BOT_TOKEN` is
+/// set, set `DAYBREAK_RECORD` too to record them to the cassette, overwriting
+/// it, they're replayed from the cassette if the token isn't set
 static CTX: Lazy<Context> = Lazy::new(|| {
+    let api_url = std::env::var("DAYBREAK_API_URL").ok();
+    let token = std::env::var("DAYBREAK_BOT_TOKEN").ok();
+    let record = std::env::var_os("DAYBREAK_RECORD").is_some();
+    let transport: Option<Arc<dyn Transport>> = match (&api_url, &token, record) {
+        (Some(_), _, _) | (None, Some(_), false) => None,
+        (None, Some(_), true) => Some(Arc::new(Cassette::record(
+            CASSETTE,
+            Arc::new(create(false)),
+        ))),
+        (None, None, _) => Some(Arc::new(Cassette::replay(CASSETTE).unwrap())),
+    };
+
     Context::new(&ContextConfig {
-        token: env!("DAYBREAK_BOT_TOKEN"),
+        token: token.as_deref().unwrap_or_default(),
+        api_url: api_url.as_deref().unwrap_or("https://discord.com/api"),
+        allow_http: api_url.is_some(),
+        transport,
+        ..ContextConfig::default()
     })
 });
 
+/// A scripted response of [`Mock`]
+#[derive(Clone, Debug)]
+enum MockResponse {
+    /// Respond with the status, headers and body after the delay
+    Respond {
+        status: StatusCode,
+        headers: Vec<(&'static str, &'static str)>,
+        body: &'static str,
+        delay: Duration,
+    },
+    /// Fail to send the request
+    Fail,
+    /// Never respond
+    Hang,
+}
+
+/// A transport that records the requests it receives and responds with the
+/// scripted responses in order
+///
+/// The last response is repeated once the others are used, it responds with
+/// no content if none are scripted
+#[derive(Debug, Default)]
+pub(crate) struct Mock {
+    /// The responses that are yet to be sent
+    responses: Mutex<VecDeque<MockResponse>>,
+    /// The requests received so far
+    requests: Mutex<Vec<Request<Body>>>,
+}
+
+impl Mock {
+    /// Script a response with the status and body
+    pub(crate) fn respond(self, status: StatusCode, body: &'static str) -> Self {
+        self.push(MockResponse::Respond {
+            status,
+            headers: vec![],
+            body,
+            delay: Duration::ZERO,
+        })
+    }
+
+    /// Add the header to the last scripted response
+    pub(crate) fn header(self, name: &'static str, value: &'static str) -> Self {
+        if let Some(MockResponse::Respond { headers, .. }) =
+            self.responses.lock().unwrap().back_mut()
+        {
+            headers.push((name, value));
+        }
+        self
+    }
+
+    /// Delay the last scripted response
+    pub(crate) fn delay(self, duration: Duration) -> Self {
+        if let Some(MockResponse::Respond { delay, .. }) = self.responses.lock().unwrap().back_mut()
+        {
+            *delay = duration;
+        }
+        self
+    }
+
+    /// Script a failure to send the request
+    pub(crate) fn fail(self) -> Self {
+        self.push(MockResponse::Fail)
+    }
+
+    /// Script a response that never arrives
+    pub(crate) fn hang(self) -> Self {
+        self.push(MockResponse::Hang)
+    }
+
+    /// The number of requests received so far
+    pub(crate) fn request_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    /// Remove and return the first request received
+    pub(crate) fn take_request(&self) -> Request<Body> {
+        self.requests.lock().unwrap().remove(0)
+    }
+
+    /// Add the response to the script
+    fn push(self, response: MockResponse) -> Self {
+        self.responses.lock().unwrap().push_back(response);
+        self
+    }
+}
+
+impl Transport for Mock {
+    fn send(&self, request: Request<Body>) -> BoxFuture<'_, Result<Response<Body>, Error>> {
+        self.requests.lock().unwrap().push(request);
+
+        let mut responses = self.responses.lock().unwrap();
+        let response = if responses.len() > 1 {
+            responses.pop_front()
+        } else {
+            responses.front().cloned()
+        };
+        drop(responses);
+
+        match response {
+            Some(MockResponse::Respond {
+                status,
+                headers,
+                body,
+                delay,
+            }) => async move {
+                sleep(delay).await;
+                let mut builder = Response::builder().status(status);
+                for (name, value) in headers {
+                    builder = builder.header(name, value);
+                }
+                Ok(builder.body(Body::from(body))?)
+            }
+            .boxed(),
+            Some(MockResponse::Fail) => async { Err(anyhow!("the mock failed")) }.boxed(),
+            Some(MockResponse::Hang) => future::pending().boxed(),
+            None => async {
+                Ok(Response::builder()
+                    .status(StatusCode::NO_CONTENT)
+                    .body(Body::empty())?)
+            }
+            .boxed(),
+        }
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 fn guild() -> Guild {
     let user_lara = User {
@@ -77,7 +244,7 @@ fn guild() -> Guild {
                 icon: None,
                 unicode_emoji: None,
                 position: 0,
-                permissions: crate::model::permission::Permissions::Administrator,
+                permissions: Permissions::Administrator.into(),
                 managed: true,
                 mentionable: false,
                 tags: Some(RoleTags {
@@ -94,7 +261,7 @@ fn guild() -> Guild {
                 icon: None,
                 unicode_emoji: None,
                 position: 1,
-                permissions: Permissions::Administrator,
+                permissions: Permissions::Administrator.into(),
                 managed: false,
                 mentionable: true,
                 tags: None,
@@ -192,7 +359,7 @@ fn guild() -> Guild {
             permission_overwrites: Some(vec![PermissionOverwrite {
                 id: Id(1_015_684_146_506_506_270),
                 kind: PermissionOverwriteKind::Role,
-                allow: Permissions::empty(),
+                allow: Permissions::empty().into(),
                 deny: Permissions::SendMessages.into(),
             }]),
             name: Some("rules".to_owned()),
@@ -231,11 +398,41 @@ fn guild() -> Guild {
 mod http;
 /// Tests for models
 mod model;
+/// Tests for computing and checking permissions
+mod permission;
 
 #[test]
 fn context_new() {
     assert_eq!(
-        Context::new(&ContextConfig { token: "foo" }).token,
-        "Bot foo"
+        Context::new(&ContextConfig {
+            token: "foo",
+            ..ContextConfig::default()
+        })
+        .token
+        .as_deref(),
+        Some("Bot foo")
+    );
+}
+
+#[test]
+fn context_api_url() {
+    assert_eq!(
+        Context::new(&ContextConfig {
+            api_url: "http://localhost:8080/api",
+            api_version: 11,
+            ..ContextConfig::default()
+        })
+        .api_url,
+        "http://localhost:8080/api/v11"
     );
 }
+
+#[test]
+fn context_proxy_url() {
+    let ctx = Context::new(&ContextConfig {
+        proxy_url: Some("http://localhost:8080"),
+        ..ContextConfig::default()
+    });
+    assert_eq!(ctx.api_url, "http://localhost:8080/api/v10");
+    assert!(ctx.rate_limiter.is_none());
+}
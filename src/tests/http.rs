@@ -1,2 +1,31 @@
+/// Tests about recording and replaying requests
+mod cassette;
 /// Tests about emoji requests
 mod emoji;
+/// Tests about errors returned from requests
+mod error;
+/// Tests about guarding against the invalid request limit
+mod invalid_request;
+/// Tests about request middleware
+mod middleware;
+/// Tests about encoding requests with files
+mod multipart;
+/// Tests about OAuth2
+mod oauth2;
+/// Tests about walking paginated resources
+mod pagination;
+/// Tests about the rate limiting proxy
+#[cfg(feature = "proxy")]
+mod proxy;
+/// Tests about the rate limiter
+mod rate_limit;
+/// Tests about building requests
+mod request;
+/// Tests about retrying requests
+mod retry;
+/// Tests about request timeouts
+mod timeout;
+/// Tests about sending requests with custom transports
+mod transport;
+/// Tests about the webhook client
+mod webhook;
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use hyper::{body::to_bytes, Body, Request, StatusCode};
+
+use crate::{
+    http::{cassette::Cassette, transport::Transport},
+    tests::Mock,
+};
+
+fn request(boundary: &str) -> Request<Body> {
+    Request::post("https://discord.com/api/v10/channels/1/messages")
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={boundary}"),
+        )
+        .body(Body::from(format!("--{boundary}--")))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn record_replay() {
+    let path = std::env::temp_dir().join("daybreak-cassette-test.json");
+
+    let cassette = Cassette::record(
+        &path,
+        Arc::new(Mock::default().respond(StatusCode::OK, "--daybreak-1234--")),
+    );
+    let response = cassette.send(request("daybreak-1234")).await.unwrap();
+    assert_eq!(
+        to_bytes(response.into_body()).await.unwrap(),
+        "--daybreak-1234--"
+    );
+
+    let cassette = Cassette::replay(&path).unwrap();
+    let response = cassette.send(request("daybreak-5678")).await.unwrap();
+    assert_eq!(
+        to_bytes(response.into_body()).await.unwrap(),
+        "--daybreak-1234--"
+    );
+    assert!(cassette
+        .send(request("daybreak-5678"))
+        .await
+        .unwrap_err()
+        .to_string()
+        .starts_with("no response for POST /api/v10/channels/1/messages"));
+}
+
+#[tokio::test]
+async fn redact_tokens() {
+    let path = std::env::temp_dir().join("daybreak-cassette-token-test.json");
+
+    let cassette = Cassette::record(&path, Arc::new(Mock::default()));
+    let response = cassette
+        .send(
+            Request::post("https://discord.com/api/v10/webhooks/1/secret?wait=true")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let recorded = std::fs::read_to_string(&path).unwrap();
+    assert!(!recorded.contains("secret"));
+    assert!(recorded.contains("/api/v10/webhooks/1/:token?wait=true"));
+
+    let cassette = Cassette::replay(&path).unwrap();
+    let response = cassette
+        .send(
+            Request::post("https://discord.com/api/v10/webhooks/1/other?wait=true")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
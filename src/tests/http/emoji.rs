@@ -1,12 +1,14 @@
 use crate::tests::{guild, CTX};
 
 #[tokio::test]
+#[ignore = "sends requests to Discord"]
 async fn emojis() {
     let guild = guild();
     assert_eq!(CTX.emojis(guild.id).await.unwrap(), guild.emojis);
 }
 
 #[tokio::test]
+#[ignore = "sends requests to Discord"]
 async fn emoji() {
     let guild = guild();
     for emoji in guild.emojis {
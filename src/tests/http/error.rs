@@ -0,0 +1,89 @@
+use anyhow::Error;
+use enumflags2::{BitFlag, BitFlags};
+use hyper::{Method, StatusCode};
+
+use crate::{
+    http::{
+        error::{DiscordApiError, ErrorCode},
+        Request,
+    },
+    model::permission::Permissions,
+    UserError,
+};
+
+#[test]
+fn discord_api_error() {
+    let api_error = DiscordApiError::new(
+        StatusCode::FORBIDDEN,
+        br#"{"code": 50001, "message": "Missing Access"}"#,
+    );
+    assert_eq!(api_error.error_code(), Some(ErrorCode::MissingAccess));
+    assert_eq!(api_error.message, "Missing Access");
+
+    let user_error = api_error
+        .user_error(
+            &Request::new(Permissions::empty(), Method::GET, "/".to_owned()),
+            None,
+        )
+        .unwrap();
+    let err = Error::new(api_error).context(user_error);
+    assert!(err.downcast_ref::<DiscordApiError>().is_some());
+    assert!(matches!(
+        err.downcast_ref::<UserError>(),
+        Some(UserError::MissingAccess)
+    ));
+}
+
+#[test]
+fn discord_api_error_missing_permissions() {
+    let api_error = DiscordApiError::new(
+        StatusCode::FORBIDDEN,
+        br#"{"code": 50013, "message": "Missing Permissions"}"#,
+    );
+    let request = Request::new(
+        Permissions::ViewChannel | Permissions::BanMembers,
+        Method::PUT,
+        "/guilds/1/bans/2".to_owned(),
+    );
+
+    for (permissions, missing) in [
+        (
+            Some(Permissions::ViewChannel.into()),
+            BitFlags::from(Permissions::BanMembers),
+        ),
+        (
+            Some(Permissions::ViewChannel | Permissions::BanMembers),
+            request.required_permissions,
+        ),
+        (None, request.required_permissions),
+    ] {
+        assert!(matches!(
+            api_error.user_error(&request, permissions),
+            Some(UserError::MissingPermissions(missing_permissions))
+                if missing_permissions == missing
+        ));
+    }
+}
+
+#[test]
+fn discord_api_error_not_json() {
+    let api_error = DiscordApiError::new(StatusCode::BAD_GATEWAY, b"Bad Gateway");
+    assert_eq!(api_error.code, None);
+    assert_eq!(api_error.error_code(), None);
+    assert_eq!(api_error.message, "Bad Gateway");
+    assert_eq!(
+        api_error.to_string(),
+        "Discord responded with 502 Bad Gateway: Bad Gateway"
+    );
+}
+
+#[test]
+fn discord_api_error_html() {
+    let body = format!("<html>{}</html>", "a".repeat(1000));
+    let api_error = DiscordApiError::new(StatusCode::BAD_GATEWAY, body.as_bytes());
+
+    assert_eq!(
+        api_error.message,
+        format!("{}... (1013 bytes)", &body[..256])
+    );
+}
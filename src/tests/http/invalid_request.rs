@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use hyper::StatusCode;
+
+use crate::{
+    http::{
+        error::{DiscordApiError, InvalidRequestLimitError},
+        invalid_request::{InvalidRequestAction, InvalidRequestPolicy},
+    },
+    model::Id,
+    tests::Mock,
+    Context, ContextConfig,
+};
+
+#[tokio::test]
+async fn refuse() {
+    let transport = Arc::new(Mock::default().respond(
+        StatusCode::FORBIDDEN,
+        r#"{"code": 50013, "message": "Missing Permissions"}"#,
+    ));
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::<Mock>::clone(&transport)),
+        invalid_request_policy: InvalidRequestPolicy {
+            threshold: 2,
+            action: InvalidRequestAction::Refuse,
+        },
+        ..ContextConfig::default()
+    });
+
+    for _ in 0..2_u8 {
+        let err = ctx.delete(Id(1), Id(2), None).await.unwrap_err();
+        assert!(err.is::<DiscordApiError>());
+    }
+    assert_eq!(ctx.invalid_request_count(), 2);
+
+    let err = ctx.delete(Id(1), Id(2), None).await.unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<InvalidRequestLimitError>()
+            .unwrap()
+            .count,
+        2
+    );
+    assert_eq!(transport.request_count(), 2);
+}
@@ -0,0 +1,131 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::Error;
+use hyper::{
+    header::{HeaderName, HeaderValue, AUTHORIZATION, USER_AGENT},
+    Body, HeaderMap, Request as HyperRequest, StatusCode,
+};
+
+use crate::{
+    http::{
+        middleware::{self, Middleware, ResponseInfo},
+        retry::RetryPolicy,
+        Request,
+    },
+    model::Id,
+    tests::Mock,
+    Context, ContextConfig,
+};
+
+/// A middleware that records the hooks called on it
+#[derive(Debug)]
+struct Recorder {
+    /// The name of the middleware, to check the order they're called in
+    name: &'static str,
+    /// The hooks called so far, shared by the middleware of a test
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware for Recorder {
+    fn on_request(&self, request: &Request, hyper_request: &mut HyperRequest<Body>) {
+        hyper_request.headers_mut().extend([(
+            HeaderName::from_static("x-middleware"),
+            HeaderValue::from_static(self.name),
+        )]);
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} request {}", self.name, request.route()));
+    }
+
+    fn on_response(&self, _: &Request, response: &ResponseInfo<'_>) {
+        assert!(response.latency >= Duration::from_millis(20));
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} response {}", self.name, response.status));
+    }
+
+    fn on_error(&self, _: &Request, error: &Error, _: Duration) {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} error {error}", self.name));
+    }
+}
+
+#[tokio::test]
+async fn hooks() {
+    let calls = Arc::default();
+    let transport = Arc::new(
+        Mock::default()
+            .fail()
+            .respond(StatusCode::BAD_GATEWAY, "")
+            .delay(Duration::from_millis(20))
+            .respond(StatusCode::NO_CONTENT, "")
+            .delay(Duration::from_millis(20)),
+    );
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::<Mock>::clone(&transport)),
+        retry_policy: RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        },
+        middleware: ["first", "second"]
+            .into_iter()
+            .map(|name| {
+                let middleware: Arc<dyn Middleware> = Arc::new(Recorder {
+                    name,
+                    calls: Arc::clone(&calls),
+                });
+                middleware
+            })
+            .collect(),
+        ..ContextConfig::default()
+    });
+
+    ctx.delete(Id(1), Id(2), None).await.unwrap();
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        [
+            "first request DELETE /guilds/1/emojis/:id",
+            "second request DELETE /guilds/1/emojis/:id",
+            "first error the mock failed",
+            "second error the mock failed",
+            "first request DELETE /guilds/1/emojis/:id",
+            "second request DELETE /guilds/1/emojis/:id",
+            "first response 502 Bad Gateway",
+            "second response 502 Bad Gateway",
+            "first request DELETE /guilds/1/emojis/:id",
+            "second request DELETE /guilds/1/emojis/:id",
+            "first response 204 No Content",
+            "second response 204 No Content",
+        ]
+    );
+    assert_eq!(transport.take_request().headers()["x-middleware"], "first");
+    assert_eq!(
+        transport
+            .take_request()
+            .headers()
+            .get_all("x-middleware")
+            .iter()
+            .collect::<Vec<_>>(),
+        ["first", "second"]
+    );
+}
+
+#[test]
+fn redacted_headers() {
+    let headers = HeaderMap::from_iter([
+        (AUTHORIZATION, HeaderValue::from_static("Bot foo")),
+        (USER_AGENT, HeaderValue::from_static("daybreak")),
+    ]);
+
+    let redacted = middleware::redacted_headers(&headers);
+    assert_eq!(redacted[AUTHORIZATION], "[redacted]");
+    assert_eq!(redacted[USER_AGENT], "daybreak");
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use enumflags2::BitFlag;
+use hyper::{body::to_bytes, Method, StatusCode};
+use serde_json::{json, Value};
+
+use crate::{
+    http::{multipart, File, Request},
+    model::permission::Permissions,
+    tests::Mock,
+    Context, ContextConfig,
+};
+
+#[test]
+fn encode() {
+    let body = multipart::encode(
+        Some(json!({ "content": "hi" })),
+        &[File {
+            filename: "daybreak\".png".to_owned(),
+            content_type: "image/png".to_owned(),
+            bytes: b"png".to_vec(),
+            description: None,
+        }],
+        "boundary",
+    )
+    .unwrap();
+
+    assert_eq!(
+        String::from_utf8(body).unwrap(),
+        format!(
+            "--boundary\r\nContent-Disposition: form-data; name=\"payload_json\"\r\nContent-Type: \
+             application/json\r\n\r\n{payload}\r\n--boundary\r\nContent-Disposition: form-data; \
+             name=\"files[0]\"; filename=\"daybreak%22.png\"\r\nContent-Type: \
+             image/png\r\n\r\npng\r\n--boundary--\r\n",
+            payload = r#"{"attachments":[{"filename":"daybreak\".png","id":0}],"content":"hi"}"#
+        )
+    );
+}
+
+#[tokio::test]
+async fn with_files() {
+    let transport = Arc::new(Mock::default().respond(StatusCode::OK, "{}"));
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::<Mock>::clone(&transport)),
+        ..ContextConfig::default()
+    });
+
+    let _response: Value = ctx
+        .request_with_params(
+            Request::new(
+                Permissions::empty(),
+                Method::POST,
+                "/channels/1/messages".to_owned(),
+            )
+            .with_files(vec![
+                File {
+                    filename: "hi.txt".to_owned(),
+                    content_type: "text/plain".to_owned(),
+                    bytes: b"hi".to_vec(),
+                    description: None,
+                },
+                File {
+                    filename: "cat.png".to_owned(),
+                    content_type: "image/png".to_owned(),
+                    bytes: b"png".to_vec(),
+                    description: Some("a cat".to_owned()),
+                },
+            ]),
+            json!({ "content": "hi" }),
+        )
+        .await
+        .unwrap();
+
+    let request = transport.take_request();
+    let content_type = request.headers()["Content-Type"].to_str().unwrap();
+    let boundary = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .unwrap()
+        .to_owned();
+    assert!(boundary.starts_with("daybreak-"));
+
+    let body = to_bytes(request.into_body()).await.unwrap();
+    assert_eq!(
+        String::from_utf8(body.to_vec()).unwrap(),
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; \
+             name=\"payload_json\"\r\nContent-Type: \
+             application/json\r\n\r\n{payload}\r\n--{boundary}\r\nContent-Disposition: form-data; \
+             name=\"files[0]\"; filename=\"hi.txt\"\r\nContent-Type: \
+             text/plain\r\n\r\nhi\r\n--{boundary}\r\nContent-Disposition: form-data; \
+             name=\"files[1]\"; filename=\"cat.png\"\r\nContent-Type: \
+             image/png\r\n\r\npng\r\n--{boundary}--\r\n",
+            payload = json!({
+                "attachments": [
+                    { "filename": "hi.txt", "id": 0_u8 },
+                    { "description": "a cat", "filename": "cat.png", "id": 1_u8 },
+                ],
+                "content": "hi",
+            })
+        )
+    );
+}
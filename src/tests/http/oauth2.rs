@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use enumflags2::BitFlags;
+use hyper::{
+    body::{to_bytes, Bytes},
+    Method, StatusCode,
+};
+
+use crate::{
+    model::{oauth2::Scope, permission::Permissions, Id},
+    oauth2::{Authorization, OAuth2Client},
+    tests::Mock,
+    Context, ContextConfig,
+};
+
+/// The response of the token endpoint
+const ACCESS_TOKEN: &str = r#"{
+    "access_token": "access",
+    "token_type": "Bearer",
+    "expires_in": 604800,
+    "refresh_token": "refresh",
+    "scope": "identify"
+}"#;
+
+#[test]
+fn authorization_url() {
+    let oauth2 = OAuth2Client::new(
+        Id(1234),
+        "secret",
+        &ContextConfig {
+            proxy_url: Some("http://localhost:8080"),
+            ..ContextConfig::default()
+        },
+    );
+
+    assert_eq!(
+        oauth2
+            .authorization_url(&Authorization {
+                scopes: &[Scope::Identify, Scope::GuildsMembersRead],
+                redirect_uri: Some("https://example.com/callback"),
+                state: Some("state"),
+                ..Authorization::default()
+            })
+            .unwrap(),
+        "https://discord.com/oauth2/authorize?response_type=code&client_id=1234&\
+         scope=identify+guilds.members.read&redirect_uri=https%3A%2F%2Fexample.com%2Fcallback&\
+         state=state"
+    );
+    assert_eq!(
+        oauth2
+            .authorization_url(&Authorization {
+                scopes: &[Scope::Bot],
+                permissions: Some(BitFlags::from(Permissions::SendMessages)),
+                guild_id: Some(Id(5678)),
+                ..Authorization::default()
+            })
+            .unwrap(),
+        "https://discord.com/oauth2/authorize?client_id=1234&scope=bot&permissions=2048&\
+         guild_id=5678"
+    );
+}
+
+/// A client for the application `1234` with the secret `secret` that sends
+/// its requests to the mock, which responds with an access token
+fn oauth2(transport: &Arc<Mock>) -> OAuth2Client {
+    OAuth2Client::new(
+        Id(1234),
+        "secret",
+        &ContextConfig {
+            transport: Some(Arc::<Mock>::clone(transport)),
+            ..ContextConfig::default()
+        },
+    )
+}
+
+/// Check that the first request the mock received was sent to the token
+/// endpoint with the application's credentials, returning its form
+async fn token_form(transport: &Mock) -> Bytes {
+    let request = transport.take_request();
+    assert_eq!(request.method(), Method::POST);
+    assert_eq!(request.uri(), "https://discord.com/api/v10/oauth2/token");
+    assert_eq!(
+        request.headers()["Content-Type"],
+        "application/x-www-form-urlencoded"
+    );
+    assert_eq!(request.headers()["Authorization"], "Basic MTIzNDpzZWNyZXQ=");
+    to_bytes(request.into_body()).await.unwrap()
+}
+
+#[tokio::test]
+async fn exchange_code() {
+    let transport = Arc::new(Mock::default().respond(StatusCode::OK, ACCESS_TOKEN));
+
+    let token = oauth2(&transport)
+        .exchange_code("code", "https://example.com/callback")
+        .await
+        .unwrap();
+    assert_eq!(token.refresh_token.as_deref(), Some("refresh"));
+
+    assert_eq!(
+        token_form(&transport).await,
+        "grant_type=authorization_code&code=code&redirect_uri=https%3A%2F%2Fexample.com%2Fcallback"
+    );
+}
+
+#[tokio::test]
+async fn refresh() {
+    let transport = Arc::new(Mock::default().respond(StatusCode::OK, ACCESS_TOKEN));
+
+    let token = oauth2(&transport).refresh("refresh").await.unwrap();
+    assert_eq!(token.access_token, "access");
+
+    assert_eq!(
+        token_form(&transport).await,
+        "grant_type=refresh_token&refresh_token=refresh"
+    );
+}
+
+#[tokio::test]
+async fn client_credentials() {
+    let transport = Arc::new(Mock::default().respond(StatusCode::OK, ACCESS_TOKEN));
+
+    let token = oauth2(&transport)
+        .client_credentials(&[Scope::Identify, Scope::Connections])
+        .await
+        .unwrap();
+    assert_eq!(token.access_token, "access");
+
+    assert_eq!(
+        token_form(&transport).await,
+        "grant_type=client_credentials&scope=identify+connections"
+    );
+}
+
+#[test]
+fn new_bearer() {
+    assert_eq!(
+        Context::new_bearer("access", &ContextConfig::default())
+            .token
+            .as_deref(),
+        Some("Bearer access")
+    );
+}
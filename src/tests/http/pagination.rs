@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use enumflags2::BitFlag;
+use futures_util::{StreamExt, TryStreamExt};
+use hyper::{Method, StatusCode};
+use serde::Deserialize;
+
+use crate::{
+    http::{error::DiscordApiError, pagination::Direction, Request},
+    model::{permission::Permissions, Id},
+    tests::Mock,
+    Context, ContextConfig,
+};
+
+/// An item of a page
+#[derive(Debug, Deserialize)]
+struct Item {
+    id: Id,
+}
+
+/// A context that sends requests to the mock transport
+fn context(transport: &Arc<Mock>) -> Context {
+    Context::new(&ContextConfig {
+        transport: Some(Arc::<Mock>::clone(transport)),
+        ..ContextConfig::default()
+    })
+}
+
+/// Walk the items with a page size of 2, returning their IDs
+async fn paginate(ctx: &Context, direction: Direction) -> Vec<Id> {
+    ctx.paginate(
+        Request::new(Permissions::empty(), Method::GET, "/items".to_owned()),
+        direction,
+        2,
+        |item: &Item| Some(item.id),
+    )
+    .map_ok(|item| item.id)
+    .try_collect()
+    .await
+    .unwrap()
+}
+
+/// The query strings of the requests the mock transport received
+fn queries(transport: &Mock) -> Vec<String> {
+    (0..transport.request_count())
+        .map(|_| {
+            transport
+                .take_request()
+                .uri()
+                .query()
+                .unwrap_or_default()
+                .to_owned()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn paginate_after() {
+    let transport = Arc::new(
+        Mock::default()
+            .respond(StatusCode::OK, r#"[{"id": "2"}, {"id": "1"}]"#)
+            .respond(StatusCode::OK, r#"[{"id": "3"}, {"id": "4"}]"#)
+            .respond(StatusCode::OK, r#"[{"id": "5"}]"#),
+    );
+
+    assert_eq!(
+        paginate(&context(&transport), Direction::After).await,
+        [Id(2), Id(1), Id(3), Id(4), Id(5)]
+    );
+    assert_eq!(
+        queries(&transport),
+        ["limit=2", "after=2&limit=2", "after=4&limit=2"]
+    );
+}
+
+#[tokio::test]
+async fn paginate_before() {
+    let transport = Arc::new(
+        Mock::default()
+            .respond(StatusCode::OK, r#"[{"id": "9"}, {"id": "8"}]"#)
+            .respond(StatusCode::OK, "[]"),
+    );
+
+    assert_eq!(
+        paginate(&context(&transport), Direction::Before).await,
+        [Id(9), Id(8)]
+    );
+    assert_eq!(queries(&transport), ["limit=2", "before=8&limit=2"]);
+}
+
+#[tokio::test]
+async fn paginate_error() {
+    let transport = Arc::new(
+        Mock::default()
+            .respond(StatusCode::OK, r#"[{"id": "1"}, {"id": "2"}]"#)
+            .respond(
+                StatusCode::NOT_FOUND,
+                r#"{"code": 10004, "message": "Unknown Guild"}"#,
+            ),
+    );
+    let ctx = context(&transport);
+    let mut items = ctx.paginate(
+        Request::new(Permissions::empty(), Method::GET, "/items".to_owned()),
+        Direction::After,
+        2,
+        |item: &Item| Some(item.id),
+    );
+
+    assert_eq!(items.next().await.unwrap().unwrap().id, Id(1));
+    assert_eq!(items.next().await.unwrap().unwrap().id, Id(2));
+    assert!(items
+        .next()
+        .await
+        .unwrap()
+        .unwrap_err()
+        .is::<DiscordApiError>());
+    assert!(items.next().await.is_none());
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn paginators() {
+    let transport = Arc::new(Mock::default().respond(StatusCode::OK, "[]"));
+    let ctx = context(&transport);
+
+    assert!(ctx.messages(Id(1)).next().await.is_none());
+    assert!(ctx
+        .reaction_users(Id(1), Id(2), "🦀")
+        .next()
+        .await
+        .is_none());
+    assert!(ctx.members(Id(1)).next().await.is_none());
+    assert!(ctx.bans(Id(1)).next().await.is_none());
+    assert!(ctx.current_user_guilds().next().await.is_none());
+    assert!(ctx
+        .scheduled_event_subscribers(Id(1), Id(2))
+        .next()
+        .await
+        .is_none());
+
+    let paths = (0..transport.request_count())
+        .map(|_| {
+            transport
+                .take_request()
+                .uri()
+                .path_and_query()
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            "/api/v10/channels/1/messages?limit=100",
+            "/api/v10/channels/1/messages/2/reactions/%F0%9F%A6%80?limit=100",
+            "/api/v10/guilds/1/members?limit=1000",
+            "/api/v10/guilds/1/bans?limit=1000",
+            "/api/v10/users/@me/guilds?limit=200",
+            "/api/v10/guilds/1/scheduled-events/2/users?with_member=true&limit=100",
+        ]
+    );
+}
@@ -0,0 +1,108 @@
+use std::{sync::Arc, time::Duration};
+
+use hyper::{body::to_bytes, Body, Request, StatusCode};
+
+use crate::{
+    http::{error::TimeoutError, proxy::Proxy, retry::RetryPolicy},
+    tests::Mock,
+    ContextConfig,
+};
+
+fn request() -> Request<Body> {
+    Request::get("http://localhost:8080/api/v10/guilds/1/emojis?foo=bar")
+        .header("Authorization", "Bot foo")
+        .header("Connection", "keep-alive")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn forward() {
+    let upstream = Arc::new(Mock::default());
+    let proxy = Proxy::new(
+        "https://discord.com",
+        &ContextConfig {
+            transport: Some(Arc::<Mock>::clone(&upstream)),
+            ..ContextConfig::default()
+        },
+    );
+
+    assert_eq!(
+        proxy.forward(request()).await.unwrap().status(),
+        StatusCode::NO_CONTENT
+    );
+
+    let forwarded = upstream.take_request();
+    assert_eq!(
+        forwarded.uri(),
+        "https://discord.com/api/v10/guilds/1/emojis?foo=bar"
+    );
+    assert_eq!(forwarded.headers()["Authorization"], "Bot foo");
+    assert!(!forwarded.headers().contains_key("Connection"));
+}
+
+#[tokio::test]
+async fn forward_error() {
+    let proxy = Proxy::new(
+        "https://discord.com",
+        &ContextConfig {
+            transport: Some(Arc::new(
+                Mock::default().respond(StatusCode::BAD_GATEWAY, ""),
+            )),
+            retry_policy: RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            },
+            ..ContextConfig::default()
+        },
+    );
+
+    assert_eq!(
+        proxy.forward(request()).await.unwrap().status(),
+        StatusCode::BAD_GATEWAY
+    );
+}
+
+#[tokio::test]
+async fn forward_transport_error() {
+    let proxy = Proxy::new(
+        "https://discord.com",
+        &ContextConfig {
+            transport: Some(Arc::new(Mock::default().fail())),
+            retry_policy: RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            },
+            ..ContextConfig::default()
+        },
+    );
+
+    let response = Proxy::error_response(&proxy.forward(request()).await.unwrap_err());
+    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    assert_eq!(
+        to_bytes(response.into_body()).await.unwrap(),
+        r#"{"code":0,"message":"the mock failed"}"#
+    );
+}
+
+#[tokio::test]
+async fn forward_timeout() {
+    let proxy = Proxy::new(
+        "https://discord.com",
+        &ContextConfig {
+            transport: Some(Arc::new(Mock::default().hang())),
+            timeout: Some(Duration::from_millis(50)),
+            ..ContextConfig::default()
+        },
+    );
+
+    let err = proxy.forward(request()).await.unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<TimeoutError>().unwrap().route,
+        "GET /api/v10/guilds/1/emojis"
+    );
+    assert_eq!(
+        Proxy::error_response(&err).status(),
+        StatusCode::GATEWAY_TIMEOUT
+    );
+}
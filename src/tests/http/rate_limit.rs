@@ -0,0 +1,155 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use hyper::{Method, StatusCode};
+
+use crate::{
+    http::{error::RateLimitError, rate_limit::Route},
+    model::Id,
+    tests::Mock,
+    Context, ContextConfig,
+};
+
+#[test]
+fn route_major_parameter() {
+    assert_eq!(
+        Route::new(&Method::GET, "/channels/1/messages/2"),
+        Route::new(&Method::GET, "/channels/1/messages/3")
+    );
+    assert_ne!(
+        Route::new(&Method::GET, "/channels/1/messages/2"),
+        Route::new(&Method::GET, "/channels/4/messages/2")
+    );
+    assert_ne!(
+        Route::new(&Method::GET, "/channels/1/messages/2"),
+        Route::new(&Method::DELETE, "/channels/1/messages/2")
+    );
+}
+
+#[test]
+fn route_string() {
+    assert_eq!(
+        Route::new(
+            &Method::PUT,
+            "/channels/1/messages/2/reactions/%F0%9F%A6%80/@me"
+        )
+        .to_string(),
+        "PUT /channels/1/messages/:id/reactions/:emoji/@me"
+    );
+    assert_eq!(
+        Route::new(&Method::POST, "/webhooks/1/token?wait=true").to_string(),
+        "POST /webhooks/1/:token"
+    );
+    assert_eq!(
+        Route::new(&Method::POST, "/interactions/1/token/callback").to_string(),
+        "POST /interactions/:id/:token/callback"
+    );
+}
+
+#[test]
+fn route_webhook_token() {
+    assert_eq!(
+        Route::new(&Method::POST, "/webhooks/1/token"),
+        Route::new(&Method::POST, "/webhooks/1/token?wait=true")
+    );
+    assert_ne!(
+        Route::new(&Method::POST, "/webhooks/1/token"),
+        Route::new(&Method::POST, "/webhooks/1/other_token")
+    );
+}
+
+#[tokio::test]
+async fn rate_limit_info() {
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::new(
+            Mock::default()
+                .respond(StatusCode::NO_CONTENT, "")
+                .header("x-ratelimit-bucket", "abcd")
+                .header("x-ratelimit-limit", "5")
+                .header("x-ratelimit-remaining", "0")
+                .header("x-ratelimit-reset-after", "60"),
+        )),
+        ..ContextConfig::default()
+    });
+    assert_eq!(ctx.rate_limits().unwrap().buckets, vec![]);
+
+    ctx.delete(Id(1), Id(2), None).await.unwrap();
+
+    let info = ctx.rate_limits().unwrap();
+    let bucket = info.exhausted().next().unwrap();
+    assert_eq!(info.global_reset_after, None);
+    assert_eq!(bucket.route, "DELETE /guilds/1/emojis/:id");
+    assert_eq!(bucket.hash.as_deref(), Some("abcd"));
+    assert_eq!((bucket.limit, bucket.remaining), (Some(5), Some(0)));
+    assert!(bucket.reset_after.unwrap() > Duration::from_secs(59));
+    assert_eq!(bucket.queued, 0);
+}
+
+#[tokio::test]
+async fn retry_rate_limited() {
+    let transport = Arc::new(
+        Mock::default()
+            .respond(
+                StatusCode::TOO_MANY_REQUESTS,
+                r#"{"message": "Rate limited", "retry_after": 0.01, "global": false}"#,
+            )
+            .respond(StatusCode::NO_CONTENT, ""),
+    );
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::<Mock>::clone(&transport)),
+        ..ContextConfig::default()
+    });
+
+    ctx.delete(Id(1), Id(2), None).await.unwrap();
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn rate_limit_error() {
+    let transport = Arc::new(
+        Mock::default()
+            .respond(
+                StatusCode::TOO_MANY_REQUESTS,
+                "<html><body>You are being rate limited</body></html>",
+            )
+            .header("retry-after", "0.01"),
+    );
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::<Mock>::clone(&transport)),
+        max_rate_limit_retries: 2,
+        ..ContextConfig::default()
+    });
+
+    let err = ctx.delete(Id(1), Id(2), None).await.unwrap_err();
+    let rate_limit_err = err.downcast_ref::<RateLimitError>().unwrap();
+    assert_eq!(rate_limit_err.route, "DELETE /guilds/1/emojis/:id");
+    assert_eq!(rate_limit_err.retry_after, Duration::from_millis(10));
+    assert!(!rate_limit_err.global);
+    assert_eq!(transport.request_count(), 3);
+}
+
+#[tokio::test]
+async fn global_rate_limit() {
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::new(
+            Mock::default()
+                .respond(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    r#"{"message": "Rate limited", "retry_after": 0.2, "global": true}"#,
+                )
+                .respond(StatusCode::NO_CONTENT, ""),
+        )),
+        max_rate_limit_retries: 0,
+        ..ContextConfig::default()
+    });
+
+    let err = ctx.delete(Id(1), Id(2), None).await.unwrap_err();
+    assert!(err.downcast_ref::<RateLimitError>().unwrap().global);
+    assert!(ctx.rate_limits().unwrap().global_reset_after.is_some());
+
+    let started_at = Instant::now();
+    ctx.delete(Id(3), Id(4), None).await.unwrap();
+    assert!(started_at.elapsed() >= Duration::from_millis(150));
+}
@@ -0,0 +1,123 @@
+use enumflags2::BitFlag;
+use hyper::{Body, Method, Response, StatusCode};
+use serde::Serialize;
+
+use crate::{
+    http::{self, error::ResponseTooLargeError, Request},
+    model::{emoji::Emoji, permission::Permissions, Id},
+    UserError,
+};
+
+#[test]
+fn request_reason() {
+    let request = Request::new(Permissions::empty(), Method::GET, "/".to_owned());
+
+    assert_eq!(
+        request
+            .clone()
+            .with_reason(Some("spam & ads"))
+            .unwrap()
+            .reason
+            .unwrap(),
+        "spam%20%26%20ads"
+    );
+    assert_eq!(
+        request
+            .clone()
+            .with_reason(Some(&"a".repeat(512)))
+            .unwrap()
+            .reason
+            .unwrap()
+            .len(),
+        512
+    );
+    let err = request
+        .with_reason(Some(&"a".repeat(513)))
+        .unwrap_err()
+        .downcast::<UserError>()
+        .unwrap();
+    assert!(matches!(err, UserError::AuditLogReasonTooLong));
+    assert_eq!(
+        err.to_string(),
+        "Please keep the reason at most 512 characters long"
+    );
+}
+
+#[test]
+fn request_query() {
+    #[derive(Serialize)]
+    struct Query {
+        before: Option<Id>,
+        after: Option<Id>,
+        name: &'static str,
+    }
+
+    assert_eq!(
+        Request::new(Permissions::empty(), Method::GET, "/".to_owned())
+            .with_query(&Query {
+                before: Some(Id(1234)),
+                after: None,
+                name: "daybreak & co",
+            })
+            .unwrap()
+            .query
+            .unwrap(),
+        "before=1234&name=daybreak+%26+co"
+    );
+
+    assert_eq!(
+        Request::new(Permissions::empty(), Method::GET, "/".to_owned())
+            .with_query(&[("with_member", true)])
+            .unwrap()
+            .with_query(&[("limit", 100_u8)])
+            .unwrap()
+            .query
+            .unwrap(),
+        "with_member=true&limit=100"
+    );
+}
+
+#[test]
+fn deserialize_no_content() {
+    http::deserialize_body::<()>(StatusCode::NO_CONTENT, b"").unwrap();
+    assert!(http::deserialize_body::<Option<Emoji>>(StatusCode::OK, b"")
+        .unwrap()
+        .is_none());
+    assert!(http::deserialize_body::<Emoji>(StatusCode::NO_CONTENT, b"")
+        .unwrap_err()
+        .to_string()
+        .contains("no content"));
+}
+
+#[test]
+fn deserialize_error_snippet() {
+    let body = format!("{{\"name\": {}", "a".repeat(300));
+    let err = http::deserialize_body::<Emoji>(StatusCode::OK, body.as_bytes()).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "the response can't be deserialized, status: 200 OK, body: {}... (309 bytes)",
+            &body[..256]
+        )
+    );
+}
+
+#[tokio::test]
+async fn read_body_max_size() {
+    let response = || Response::new(Body::from(vec![0; 100]));
+
+    assert_eq!(
+        http::read_body(response(), Some(100)).await.unwrap().len(),
+        100
+    );
+    assert_eq!(
+        http::read_body(response(), Some(99))
+            .await
+            .unwrap_err()
+            .downcast_ref::<ResponseTooLargeError>()
+            .unwrap()
+            .max_size,
+        99
+    );
+}
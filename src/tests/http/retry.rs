@@ -0,0 +1,74 @@
+use std::{sync::Arc, time::Duration};
+
+use hyper::{Method, StatusCode};
+
+use crate::{
+    http::{error::DiscordApiError, retry::RetryPolicy},
+    model::{emoji::CreateEmoji, Id},
+    tests::Mock,
+    Context, ContextConfig,
+};
+
+#[test]
+fn retry_delay() {
+    let policy = RetryPolicy::default();
+
+    assert!(policy.retry_delay(1, &Method::GET, true).is_some());
+    assert!(policy.retry_delay(1, &Method::POST, false).is_some());
+    assert!(policy.retry_delay(1, &Method::POST, true).is_none());
+    assert!(policy.retry_delay(3, &Method::GET, false).is_none());
+    assert!(RetryPolicy {
+        retry_non_idempotent: true,
+        ..policy
+    }
+    .retry_delay(1, &Method::POST, true)
+    .is_some());
+}
+
+#[test]
+fn retry_backoff() {
+    let policy = RetryPolicy {
+        base_delay: Duration::from_secs(1),
+        max_delay: Duration::from_secs(3),
+        ..RetryPolicy::default()
+    };
+
+    for (attempt, max_delay) in [(1, 1), (2, 2), (3, 3), (10, 3)] {
+        let delay = policy.delay(attempt);
+        assert!(delay <= Duration::from_secs(max_delay));
+        assert!(delay >= Duration::from_secs(max_delay) / 2);
+    }
+}
+
+#[tokio::test]
+async fn post_not_retried() {
+    let transport = Arc::new(
+        Mock::default()
+            .respond(StatusCode::BAD_GATEWAY, "<html>502 Bad Gateway</html>")
+            .respond(StatusCode::CREATED, r#"{"id": "2", "name": "blobcat"}"#),
+    );
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::<Mock>::clone(&transport)),
+        ..ContextConfig::default()
+    });
+
+    let err = ctx
+        .create(
+            Id(1),
+            CreateEmoji {
+                name: "blobcat".to_owned(),
+                image: "data:image/png;base64,".to_owned(),
+                roles: vec![],
+            },
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<DiscordApiError>().unwrap().status,
+        StatusCode::BAD_GATEWAY
+    );
+
+    assert_eq!(transport.request_count(), 1);
+    assert_eq!(transport.take_request().method(), Method::POST);
+}
@@ -0,0 +1,73 @@
+use std::{sync::Arc, time::Duration};
+
+use enumflags2::BitFlag;
+use hyper::{Method, StatusCode};
+use tokio::time;
+
+use crate::{
+    http::{error::TimeoutError, Request},
+    model::{permission::Permissions, Id},
+    tests::Mock,
+    Context, ContextConfig,
+};
+
+#[tokio::test]
+async fn timeout() {
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::new(
+            Mock::default().hang().respond(StatusCode::NO_CONTENT, ""),
+        )),
+        timeout: Some(Duration::from_millis(50)),
+        ..ContextConfig::default()
+    });
+
+    let err = ctx.delete(Id(1), Id(2), None).await.unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<TimeoutError>().unwrap().route,
+        "DELETE /guilds/1/emojis/:id"
+    );
+
+    ctx.delete(Id(1), Id(2), None).await.unwrap();
+}
+
+#[tokio::test]
+async fn request_timeout() {
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::new(Mock::default().hang())),
+        timeout: Some(Duration::from_secs(10)),
+        ..ContextConfig::default()
+    });
+
+    let err = ctx
+        .empty_request::<()>(
+            Request::new(Permissions::empty(), Method::GET, "/gateway".to_owned())
+                .with_timeout(Duration::from_millis(50)),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<TimeoutError>().unwrap().timeout,
+        Duration::from_millis(50)
+    );
+}
+
+#[tokio::test]
+async fn cancel_releases_bucket() {
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::new(
+            Mock::default().hang().respond(StatusCode::NO_CONTENT, ""),
+        )),
+        timeout: None,
+        ..ContextConfig::default()
+    });
+
+    let _elapsed = time::timeout(Duration::from_millis(50), ctx.delete(Id(1), Id(2), None))
+        .await
+        .unwrap_err();
+
+    time::timeout(Duration::from_secs(1), ctx.delete(Id(1), Id(2), None))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ctx.rate_limits().unwrap().buckets[0].queued, 0);
+}
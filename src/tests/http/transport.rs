@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use hyper::{Method, StatusCode};
+
+use crate::{model::Id, tests::Mock, Context, ContextConfig};
+
+#[tokio::test]
+async fn custom_transport() {
+    let transport = Arc::new(Mock::default().respond(
+        StatusCode::OK,
+        r#"{"id": "2", "name": "blobcat", "animated": false}"#,
+    ));
+    let ctx = Context::new(&ContextConfig {
+        token: "token",
+        transport: Some(Arc::<Mock>::clone(&transport)),
+        ..ContextConfig::default()
+    });
+
+    let emoji = ctx.emoji(Id(1), Id(2)).await.unwrap();
+    assert_eq!(emoji.id, Some(Id(2)));
+    assert_eq!(emoji.name.as_deref(), Some("blobcat"));
+
+    assert_eq!(transport.request_count(), 1);
+    let request = transport.take_request();
+    assert_eq!(request.method(), Method::GET);
+    assert_eq!(
+        request.uri(),
+        "https://discord.com/api/v10/guilds/1/emojis/2"
+    );
+    assert_eq!(request.headers()["Authorization"], "Bot token");
+    assert_eq!(
+        request.headers()["User-Agent"],
+        "DiscordBot (https://github.com/gaybreak/daybreak 0.1)"
+    );
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use hyper::{Body, Method, Request, StatusCode};
+
+use crate::{
+    http::File,
+    model::{
+        webhook::{EditWebhookMessage, ExecuteWebhook},
+        Id,
+    },
+    tests::Mock,
+    webhook::WebhookClient,
+    ContextConfig,
+};
+
+#[test]
+fn from_url() {
+    for url in [
+        "https://discord.com/api/webhooks/1234/token",
+        "https://discord.com/api/v10/webhooks/1234/token/",
+        "https://discordapp.com/api/webhooks/1234/token?wait=true",
+    ] {
+        let webhook = WebhookClient::from_url(url, &ContextConfig::default()).unwrap();
+        assert_eq!(webhook.id(), Id(1234));
+        assert_eq!(webhook.token, "token");
+    }
+
+    for url in [
+        "https://discord.com/api/webhooks/1234",
+        "https://discord.com/api/webhooks/foo/token",
+        "https://discord.com/api/channels/1234/token",
+    ] {
+        assert_eq!(
+            WebhookClient::from_url(url, &ContextConfig::default())
+                .unwrap_err()
+                .to_string(),
+            format!("{url} isn't a webhook URL")
+        );
+    }
+}
+
+/// A webhook client that sends requests to the mock transport, with a bot
+/// token in the config that it should ignore
+fn webhook(transport: &Arc<Mock>) -> WebhookClient {
+    WebhookClient::new(
+        Id(1234),
+        "token",
+        &ContextConfig {
+            token: "bot token",
+            transport: Some(Arc::<Mock>::clone(transport)),
+            ..ContextConfig::default()
+        },
+    )
+}
+
+/// The method of the request and its path with the query string, checking
+/// that it isn't authorized with the bot token
+fn method_and_path(request: &Request<Body>) -> (Method, String) {
+    assert!(!request.headers().contains_key("Authorization"));
+
+    (
+        request.method().clone(),
+        request.uri().path_and_query().unwrap().to_string(),
+    )
+}
+
+#[tokio::test]
+async fn execute() {
+    let transport = Arc::new(Mock::default());
+
+    webhook(&transport)
+        .execute(ExecuteWebhook::default(), vec![], Some(Id(5678)))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        method_and_path(&transport.take_request()),
+        (
+            Method::POST,
+            "/api/v10/webhooks/1234/token?wait=false&thread_id=5678".to_owned()
+        )
+    );
+}
+
+#[tokio::test]
+async fn execute_and_wait() {
+    let transport = Arc::new(Mock::default().respond(
+        StatusCode::OK,
+        r#"{"id": "1", "channel_id": "2", "content": "hi"}"#,
+    ));
+
+    let message = webhook(&transport)
+        .execute_and_wait(
+            ExecuteWebhook {
+                content: Some("hi".to_owned()),
+                ..ExecuteWebhook::default()
+            },
+            vec![File {
+                filename: "hi.txt".to_owned(),
+                content_type: "text/plain".to_owned(),
+                bytes: b"hi".to_vec(),
+                description: None,
+            }],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(message.id, Id(1));
+
+    let request = transport.take_request();
+    assert!(request.headers()["Content-Type"]
+        .to_str()
+        .unwrap()
+        .starts_with("multipart/form-data; boundary="));
+    assert_eq!(
+        method_and_path(&request),
+        (
+            Method::POST,
+            "/api/v10/webhooks/1234/token?wait=true".to_owned()
+        )
+    );
+}
+
+#[tokio::test]
+async fn edit_message() {
+    let transport =
+        Arc::new(Mock::default().respond(StatusCode::OK, r#"{"id": "1", "channel_id": "2"}"#));
+
+    let message = webhook(&transport)
+        .edit_message(Id(1), EditWebhookMessage::default(), vec![], Some(Id(5678)))
+        .await
+        .unwrap();
+    assert_eq!(message.channel_id, Id(2));
+
+    assert_eq!(
+        method_and_path(&transport.take_request()),
+        (
+            Method::PATCH,
+            "/api/v10/webhooks/1234/token/messages/1?thread_id=5678".to_owned()
+        )
+    );
+}
+
+#[tokio::test]
+async fn delete_message() {
+    let transport = Arc::new(Mock::default());
+
+    webhook(&transport)
+        .delete_message(Id(1), None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        method_and_path(&transport.take_request()),
+        (
+            Method::DELETE,
+            "/api/v10/webhooks/1234/token/messages/1".to_owned()
+        )
+    );
+}
@@ -1,7 +1,7 @@
 use time::OffsetDateTime;
 
 use crate::model::{
-    permission::{self, Permissions},
+    permission::{self, PermissionSet, Permissions},
     Id,
 };
 
@@ -13,6 +13,14 @@ fn id_timestamp() {
     );
 }
 
+#[test]
+fn id_serialize() {
+    assert_eq!(
+        serde_json::to_string(&Id(258_568_289_746_288_641)).unwrap(),
+        "\"258568289746288641\""
+    );
+}
+
 #[test]
 fn permissions_pretty_string() {
     assert_eq!(
@@ -20,3 +28,14 @@ fn permissions_pretty_string() {
         "- Create Invite\n- Kick Members"
     );
 }
+
+#[test]
+fn permission_set_serde() {
+    let json = format!("\"{}\"", 1_071_698_660_929_u64 | (1 << 60_u8));
+    let permissions: PermissionSet = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(permissions.flags().bits(), 1_071_698_660_929);
+    assert!(permissions.contains(Permissions::CreateInstantInvite | Permissions::ViewChannel));
+    assert!(!permissions.contains(Permissions::Administrator));
+    assert_eq!(serde_json::to_string(&permissions).unwrap(), json);
+}
@@ -0,0 +1,417 @@
+use std::sync::Arc;
+
+use enumflags2::BitFlags;
+use hyper::Method;
+use serde_json::json;
+use time::{Duration, OffsetDateTime};
+
+use crate::{
+    http::Request,
+    model::{
+        channel::{Channel, ChannelType, PermissionOverwrite, PermissionOverwriteKind},
+        member::Member,
+        permission::Permissions,
+        role::Role,
+        Id,
+    },
+    tests::{guild, Mock},
+    Context, ContextConfig, UserError,
+};
+
+/// A role with the given ID and permissions
+fn role(id: Id, permissions: Permissions) -> Role {
+    Role {
+        id,
+        permissions: permissions.into(),
+        ..guild().roles.swap_remove(1)
+    }
+}
+
+/// A role with the given ID and position
+fn positioned_role(id: Id, position: u8) -> Role {
+    Role {
+        position,
+        name: format!("role {}", id.0),
+        ..role(id, Permissions::ViewChannel)
+    }
+}
+
+/// A member of the user with the given ID, with the given roles
+fn member(user_id: Id, roles: &[Id]) -> Member {
+    serde_json::from_value(json!({
+        "user": { "id": user_id },
+        "roles": roles,
+    }))
+    .unwrap()
+}
+
+/// An overwrite of the role or member with the given ID
+fn overwrite(
+    id: Id,
+    kind: PermissionOverwriteKind,
+    allow: BitFlags<Permissions>,
+    deny: BitFlags<Permissions>,
+) -> PermissionOverwrite {
+    PermissionOverwrite {
+        id,
+        kind,
+        allow: allow.into(),
+        deny: deny.into(),
+    }
+}
+
+/// The channel of the guild fixture with the given overwrites
+fn channel(overwrites: Vec<PermissionOverwrite>) -> Channel {
+    Channel {
+        permission_overwrites: Some(overwrites),
+        ..guild().channels.unwrap().swap_remove(0)
+    }
+}
+
+#[test]
+fn member_permissions() {
+    let mut guild = guild();
+    guild.roles = vec![
+        role(guild.id, Permissions::ViewChannel),
+        role(Id(1), Permissions::SendMessages),
+        role(Id(2), Permissions::BanMembers),
+        role(Id(3), Permissions::Administrator),
+    ];
+
+    assert_eq!(
+        guild.member_permissions(&member(Id(10), &[])),
+        Permissions::ViewChannel
+    );
+    assert_eq!(
+        guild.member_permissions(&member(Id(10), &[Id(1)])),
+        Permissions::ViewChannel | Permissions::SendMessages
+    );
+    assert_eq!(
+        guild.member_permissions(&member(Id(10), &[Id(1), Id(3)])),
+        BitFlags::all()
+    );
+    assert_eq!(
+        guild.member_permissions(&member(guild.owner_id, &[])),
+        BitFlags::all()
+    );
+}
+
+#[test]
+fn restricted_permissions() {
+    let mut guild = guild();
+    guild.roles = vec![
+        role(guild.id, Permissions::ViewChannel),
+        role(Id(1), Permissions::SendMessages),
+        role(Id(2), Permissions::ReadMessageHistory),
+        role(Id(3), Permissions::Administrator),
+    ];
+    let mut member = member(Id(10), &[Id(1), Id(2)]);
+    let channel = channel(vec![]);
+
+    member.communication_disabled_until = Some(OffsetDateTime::now_utc() - Duration::MINUTE);
+    assert!(!member.is_timed_out());
+    assert_eq!(
+        guild.member_permissions(&member),
+        Permissions::ViewChannel | Permissions::SendMessages | Permissions::ReadMessageHistory
+    );
+
+    member.communication_disabled_until = Some(OffsetDateTime::now_utc() + Duration::MINUTE);
+    assert!(member.is_timed_out());
+    assert_eq!(
+        guild.member_permissions(&member),
+        Permissions::ViewChannel | Permissions::ReadMessageHistory
+    );
+    assert_eq!(
+        guild.channel_permissions(&channel, &member),
+        Permissions::ViewChannel | Permissions::ReadMessageHistory
+    );
+
+    member.communication_disabled_until = None;
+    member.pending = Some(true);
+    assert_eq!(
+        guild.channel_permissions(&channel, &member),
+        Permissions::ViewChannel | Permissions::ReadMessageHistory
+    );
+
+    member.roles.push(Id(3));
+    assert_eq!(guild.member_permissions(&member), BitFlags::all());
+}
+
+#[test]
+fn channel_permissions() {
+    let mut guild = guild();
+    guild.roles = vec![
+        role(guild.id, Permissions::ViewChannel),
+        role(Id(3), Permissions::SendMessages),
+        role(Id(4), Permissions::EmbedLinks),
+    ];
+    let member = member(Id(10), &[Id(1), Id(2), Id(3), Id(4)]);
+
+    let text_channel = channel(vec![
+        overwrite(
+            guild.id,
+            PermissionOverwriteKind::Role,
+            BitFlags::empty(),
+            Permissions::SendMessages.into(),
+        ),
+        overwrite(
+            Id(1),
+            PermissionOverwriteKind::Role,
+            Permissions::SendMessages.into(),
+            BitFlags::empty(),
+        ),
+        overwrite(
+            Id(2),
+            PermissionOverwriteKind::Role,
+            Permissions::AttachFiles.into(),
+            Permissions::SendMessages.into(),
+        ),
+    ]);
+    assert_eq!(
+        guild.channel_permissions(&text_channel, &member),
+        Permissions::ViewChannel
+            | Permissions::SendMessages
+            | Permissions::EmbedLinks
+            | Permissions::AttachFiles
+    );
+
+    let muted_channel = channel(vec![overwrite(
+        Id(10),
+        PermissionOverwriteKind::Member,
+        BitFlags::empty(),
+        Permissions::SendMessages.into(),
+    )]);
+    assert_eq!(
+        guild.channel_permissions(&muted_channel, &member),
+        Permissions::ViewChannel
+    );
+
+    let hidden_channel = channel(vec![overwrite(
+        guild.id,
+        PermissionOverwriteKind::Role,
+        BitFlags::empty(),
+        Permissions::ViewChannel.into(),
+    )]);
+    assert_eq!(
+        guild.channel_permissions(&hidden_channel, &member),
+        BitFlags::empty()
+    );
+}
+
+#[tokio::test]
+async fn missing_permissions() {
+    let mut guild = guild();
+    guild.roles = vec![role(guild.id, Permissions::ViewChannel)];
+    let ctx = Context::new(&ContextConfig::default());
+    ctx.cache_permissions(&guild, &member(Id(10), &[]));
+    assert_eq!(
+        ctx.permissions(guild.id),
+        Some(Permissions::ViewChannel.into())
+    );
+
+    let err = ctx
+        .empty_request::<()>(
+            Request::new(
+                Permissions::ViewChannel | Permissions::BanMembers,
+                Method::GET,
+                format!("/guilds/{}/bans", guild.id),
+            )
+            .with_guild(guild.id),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast::<UserError>(),
+        Ok(UserError::MissingPermissions(missing)) if missing == Permissions::BanMembers
+    ));
+
+    ctx.uncache_permissions(guild.id);
+    assert_eq!(ctx.permissions(guild.id), None);
+
+    let mut history_channel = channel(vec![overwrite(
+        guild.id,
+        PermissionOverwriteKind::Role,
+        Permissions::ReadMessageHistory.into(),
+        BitFlags::empty(),
+    )]);
+    history_channel.id = Id(20);
+    ctx.cache_permissions(&guild, &member(Id(10), &[]));
+    ctx.cache_channel(&history_channel);
+    assert_eq!(
+        ctx.channel_permissions(history_channel.id),
+        Some(Permissions::ViewChannel | Permissions::ReadMessageHistory)
+    );
+
+    let err = ctx
+        .empty_request::<()>(
+            Request::new(
+                Permissions::ViewChannel | Permissions::ManageMessages,
+                Method::DELETE,
+                format!("/channels/{}/messages/1", history_channel.id),
+            )
+            .with_channel(history_channel.id),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Please give the bot these permissions in <#20>:\n- Manage Messages"
+    );
+
+    ctx.uncache_permissions(guild.id);
+    assert_eq!(ctx.channel_permissions(history_channel.id), None);
+}
+
+#[test]
+fn role_hierarchy() {
+    let mut guild = guild();
+    guild.roles = vec![
+        Role {
+            name: "@everyone".to_owned(),
+            ..positioned_role(guild.id, 0)
+        },
+        positioned_role(Id(1), 1),
+        positioned_role(Id(2), 2),
+        positioned_role(Id(3), 3),
+        positioned_role(Id(4), 2),
+    ];
+    let bot = member(Id(10), &[Id(1), Id(2)]);
+
+    assert_eq!(guild.highest_role(&bot).unwrap().id, Id(2));
+    guild.check_role_hierarchy(&bot, Id(1)).unwrap();
+    guild
+        .check_member_hierarchy(&bot, &member(Id(11), &[Id(4)]))
+        .unwrap();
+    assert_eq!(
+        guild
+            .check_role_hierarchy(&bot, Id(2))
+            .unwrap_err()
+            .to_string(),
+        "The bot's role must be above @role 2"
+    );
+    assert!(matches!(
+        guild.check_role_hierarchy(&bot, Id(5)),
+        Err(UserError::UnknownRole)
+    ));
+    assert!(matches!(
+        guild.check_member_hierarchy(&bot, &member(Id(11), &[Id(1), Id(3)])),
+        Err(UserError::RoleTooHigh(name)) if name == "role 3"
+    ));
+    assert_eq!(
+        guild
+            .check_member_hierarchy(&member(Id(10), &[]), &member(Id(11), &[]))
+            .unwrap_err()
+            .to_string(),
+        "The bot's role must be above @everyone"
+    );
+    assert!(matches!(
+        guild.check_member_hierarchy(&bot, &member(guild.owner_id, &[])),
+        Err(UserError::MemberIsOwner)
+    ));
+    guild
+        .check_member_hierarchy(&member(guild.owner_id, &[]), &member(Id(11), &[Id(3)]))
+        .unwrap();
+    assert!(matches!(
+        guild.check_role_hierarchy(&member(guild.owner_id, &[]), Id(5)),
+        Err(UserError::UnknownRole)
+    ));
+}
+
+#[test]
+fn cache_guild_channels() {
+    let mut guild = guild();
+    guild.roles = vec![role(guild.id, Permissions::ViewChannel)];
+
+    let mut history_channel = channel(vec![overwrite(
+        guild.id,
+        PermissionOverwriteKind::Role,
+        Permissions::ReadMessageHistory.into(),
+        BitFlags::empty(),
+    )]);
+    history_channel.guild_id = None;
+    let thread = Channel {
+        id: Id(30),
+        channel_type: ChannelType::GuildPublicThread,
+        parent_id: Some(history_channel.id),
+        permission_overwrites: None,
+        ..history_channel.clone()
+    };
+    guild.channels = Some(vec![history_channel.clone()]);
+    guild.threads = Some(vec![thread.clone()]);
+
+    let ctx = Context::new(&ContextConfig::default());
+    ctx.cache_permissions(&guild, &member(Id(10), &[]));
+    for channel_id in [history_channel.id, thread.id] {
+        assert_eq!(
+            ctx.channel_permissions(channel_id),
+            Some(Permissions::ViewChannel | Permissions::ReadMessageHistory)
+        );
+    }
+}
+
+#[tokio::test]
+async fn uncached_channel() {
+    let mut guild = guild();
+    guild.roles = vec![role(guild.id, Permissions::ViewChannel)];
+    let transport = Arc::new(Mock::default());
+    let ctx = Context::new(&ContextConfig {
+        transport: Some(Arc::<Mock>::clone(&transport)),
+        ..ContextConfig::default()
+    });
+    ctx.cache_permissions(&guild, &member(Id(10), &[]));
+
+    ctx.empty_request::<()>(
+        Request::new(
+            Permissions::ViewChannel | Permissions::SendMessages,
+            Method::POST,
+            "/channels/20/messages".to_owned(),
+        )
+        .with_guild(guild.id)
+        .with_channel(Id(20)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(transport.request_count(), 1);
+}
+
+#[test]
+fn cache_thread() {
+    let mut guild = guild();
+    guild.roles = vec![role(guild.id, Permissions::ViewChannel)];
+    let ctx = Context::new(&ContextConfig::default());
+    ctx.cache_permissions(&guild, &member(Id(10), &[]));
+
+    let mut parent = channel(vec![]);
+    parent.id = Id(20);
+    let thread = Channel {
+        id: Id(30),
+        channel_type: ChannelType::GuildPrivateThread,
+        parent_id: Some(parent.id),
+        permission_overwrites: None,
+        ..parent.clone()
+    };
+
+    ctx.cache_channel(&thread);
+    assert_eq!(ctx.channel_permissions(thread.id), None);
+
+    ctx.cache_channel(&parent);
+    assert_eq!(
+        ctx.channel_permissions(thread.id),
+        Some(Permissions::ViewChannel.into())
+    );
+
+    parent.permission_overwrites = Some(vec![overwrite(
+        guild.id,
+        PermissionOverwriteKind::Role,
+        Permissions::ReadMessageHistory.into(),
+        BitFlags::empty(),
+    )]);
+    ctx.cache_channel(&parent);
+    assert_eq!(
+        ctx.channel_permissions(thread.id),
+        Some(Permissions::ViewChannel | Permissions::ReadMessageHistory)
+    );
+
+    ctx.uncache_channel(parent.id);
+    assert_eq!(ctx.channel_permissions(thread.id), None);
+}
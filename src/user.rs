@@ -0,0 +1,45 @@
+use anyhow::Error;
+use enumflags2::BitFlag;
+use futures_util::stream::BoxStream;
+use hyper::Method;
+
+use super::Context;
+use crate::{
+    http::{pagination::Direction, Request},
+    model::{guild::CurrentUserGuild, permission::Permissions, user::User},
+};
+
+impl Context {
+    /// Get the user the context authenticates as, this is the bot, or the
+    /// user if the context was created with [`Self::new_bearer`]
+    #[doc = discord_url!("https://discord.com/developers/docs/resources/user#get-current-user")]
+    #[doc = http_errors_doc!()]
+    pub async fn current_user(&self) -> Result<User, Error> {
+        self.empty_request(Request::new(
+            Permissions::empty(),
+            Method::GET,
+            "/users/@me".to_owned(),
+        ))
+        .await
+    }
+
+    /// Walk the guilds the current user is in, this requires the `guilds`
+    /// scope if the context was created with [`Self::new_bearer`]
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/resources/user#get-current-user-guilds"
+    )]
+    #[doc = http_errors_doc!()]
+    #[must_use]
+    pub fn current_user_guilds(&self) -> BoxStream<'_, Result<CurrentUserGuild, Error>> {
+        self.paginate(
+            Request::new(
+                Permissions::empty(),
+                Method::GET,
+                "/users/@me/guilds".to_owned(),
+            ),
+            Direction::After,
+            200,
+            |guild: &CurrentUserGuild| Some(guild.id),
+        )
+    }
+}
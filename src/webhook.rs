@@ -0,0 +1,218 @@
+use anyhow::{anyhow, Error};
+use enumflags2::BitFlag;
+use hyper::Method;
+use serde::Serialize;
+
+use crate::{
+    http::{File, Request},
+    model::{
+        message::Message,
+        permission::Permissions,
+        webhook::{EditWebhookMessage, ExecuteWebhook},
+        Id,
+    },
+    Context, ContextConfig,
+};
+
+/// The query string of webhook requests
+#[derive(Serialize)]
+struct WebhookQuery {
+    /// Whether to wait for the message to be sent and return it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wait: Option<bool>,
+    /// The thread in the webhook's channel to send the message in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_id: Option<Id>,
+}
+
+/// A client that executes a webhook and manages its messages, authenticating
+/// with the webhook's token, so it doesn't need a bot token
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use daybreak::{model::webhook::ExecuteWebhook, webhook::WebhookClient, ContextConfig};
+///
+/// # async fn run() -> Result<(), anyhow::Error> {
+/// let webhook = WebhookClient::from_url(
+///     "https://discord.com/api/webhooks/1234/my-totally-real-token",
+///     &ContextConfig::default(),
+/// )?;
+///
+/// webhook
+///     .execute(
+///         ExecuteWebhook {
+///             content: Some("The build passed!".to_owned()),
+///             username: Some("CI".to_owned()),
+///             ..ExecuteWebhook::default()
+///         },
+///         vec![],
+///         None,
+///     )
+///     .await?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct WebhookClient {
+    /// The context the requests are made with, which has no token
+    pub(crate) ctx: Context,
+    /// The ID of the webhook
+    pub(crate) id: Id,
+    /// The token of the webhook
+    pub(crate) token: String,
+}
+
+impl WebhookClient {
+    /// Create a client for the webhook with the given ID and token
+    ///
+    /// The requests are made with a context created from the config, but
+    /// `config.token` is ignored
+    #[must_use]
+    pub fn new(id: Id, token: &str, config: &ContextConfig<'_>) -> Self {
+        Self {
+            ctx: Context::new(&ContextConfig {
+                token: "",
+                ..config.clone()
+            }),
+            id,
+            token: token.to_owned(),
+        }
+    }
+
+    /// Create a client for the webhook with the given URL, such as
+    /// `https://discord.com/api/webhooks/{id}/{token}`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL doesn't have a webhook ID and token
+    pub fn from_url(url: &str, config: &ContextConfig<'_>) -> Result<Self, Error> {
+        let invalid_url = || anyhow!("{url} isn't a webhook URL");
+
+        let (_, path) = url.split_once("/webhooks/").ok_or_else(invalid_url)?;
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let (id, token) = path.split_once('/').ok_or_else(invalid_url)?;
+        let token = token.trim_end_matches('/');
+        if token.is_empty() || token.contains('/') {
+            return Err(invalid_url());
+        }
+
+        Ok(Self::new(
+            Id(id.parse().map_err(|_| invalid_url())?),
+            token,
+            config,
+        ))
+    }
+
+    /// The ID of the webhook
+    #[must_use]
+    pub const fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Send a message with the webhook, in the thread with the given ID if
+    /// it's given, without waiting for it to be sent
+    ///
+    /// The files are uploaded as attachments of the message
+    #[doc = discord_url!("https://discord.com/developers/docs/resources/webhook#execute-webhook")]
+    #[doc = http_errors_doc!()]
+    pub async fn execute(
+        &self,
+        message: ExecuteWebhook,
+        files: Vec<File>,
+        thread_id: Option<Id>,
+    ) -> Result<(), Error> {
+        self.ctx
+            .request_with_params(self.execute_request(false, files, thread_id)?, message)
+            .await
+    }
+
+    /// Send a message with the webhook, in the thread with the given ID if
+    /// it's given, waiting for it to be sent and returning it
+    ///
+    /// The files are uploaded as attachments of the message
+    #[doc = discord_url!("https://discord.com/developers/docs/resources/webhook#execute-webhook")]
+    #[doc = http_errors_doc!()]
+    pub async fn execute_and_wait(
+        &self,
+        message: ExecuteWebhook,
+        files: Vec<File>,
+        thread_id: Option<Id>,
+    ) -> Result<Message, Error> {
+        self.ctx
+            .request_with_params(self.execute_request(true, files, thread_id)?, message)
+            .await
+    }
+
+    /// Edit a message sent with the webhook, in the thread with the given ID
+    /// if it's given
+    ///
+    /// The files are uploaded as new attachments of the message
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/resources/webhook#edit-webhook-message"
+    )]
+    #[doc = http_errors_doc!()]
+    pub async fn edit_message(
+        &self,
+        message_id: Id,
+        message: EditWebhookMessage,
+        files: Vec<File>,
+        thread_id: Option<Id>,
+    ) -> Result<Message, Error> {
+        self.ctx
+            .request_with_params(
+                self.message_request(Method::PATCH, message_id, thread_id)?
+                    .with_files(files),
+                message,
+            )
+            .await
+    }
+
+    /// Delete a message sent with the webhook, in the thread with the given ID
+    /// if it's given
+    #[doc = discord_url!(
+        "https://discord.com/developers/docs/resources/webhook#delete-webhook-message"
+    )]
+    #[doc = http_errors_doc!()]
+    pub async fn delete_message(&self, message_id: Id, thread_id: Option<Id>) -> Result<(), Error> {
+        self.ctx
+            .empty_request(self.message_request(Method::DELETE, message_id, thread_id)?)
+            .await
+    }
+
+    /// The request to execute the webhook
+    fn execute_request(
+        &self,
+        wait: bool,
+        files: Vec<File>,
+        thread_id: Option<Id>,
+    ) -> Result<Request, Error> {
+        Ok(Request::new(
+            Permissions::empty(),
+            Method::POST,
+            format!("/webhooks/{}/{}", self.id, self.token),
+        )
+        .with_query(&WebhookQuery {
+            wait: Some(wait),
+            thread_id,
+        })?
+        .with_files(files))
+    }
+
+    /// The request to the webhook message with the given ID
+    fn message_request(
+        &self,
+        method: Method,
+        message_id: Id,
+        thread_id: Option<Id>,
+    ) -> Result<Request, Error> {
+        Request::new(
+            Permissions::empty(),
+            method,
+            format!("/webhooks/{}/{}/messages/{message_id}", self.id, self.token),
+        )
+        .with_query(&WebhookQuery {
+            wait: None,
+            thread_id,
+        })
+    }
+}